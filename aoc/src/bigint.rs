@@ -0,0 +1,171 @@
+// Unsigned integers of any size, for answers that outgrow `u128`.
+//
+// Only what the days need: building numbers up bit by bit, adding, multiplying and printing them
+// in decimal. Digits are stored least significant first in base 2^32, with no leading zero digits
+// so equal numbers have equal digits.
+
+use std::fmt;
+use std::ops::{Add, Mul};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// Append a binary digit, i.e. `self * 2 + bit`.
+    pub fn push_bit(&mut self, bit: bool) {
+        let mut carry = u32::from(bit);
+        for digit in &mut self.digits {
+            let next = *digit >> 31;
+            *digit = *digit << 1 | carry;
+            carry = next;
+        }
+        if carry != 0 {
+            self.digits.push(carry);
+        }
+    }
+
+    /// The number with binary digits `bits`, most significant first.
+    pub fn from_bits(bits: impl IntoIterator<Item = bool>) -> Self {
+        let mut n = Self::zero();
+        for bit in bits {
+            n.push_bit(bit);
+        }
+        n
+    }
+
+    fn trim(mut self) -> Self {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        self
+    }
+
+    // Divide in place by a small `divisor`, returning the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for digit in self.digits.iter_mut().rev() {
+            let n = remainder << 32 | u64::from(*digit);
+            *digit = (n / u64::from(divisor)) as u32;
+            remainder = n % u64::from(divisor);
+        }
+        *self = std::mem::take(self).trim();
+        remainder as u32
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut n: u128) -> Self {
+        let mut digits = Vec::new();
+        while n != 0 {
+            digits.push(n as u32);
+            n >>= 32;
+        }
+        Self { digits }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        Self::from(u128::from(n))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.digits.len() >= other.digits.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut digits = Vec::with_capacity(long.digits.len() + 1);
+        let mut carry = 0u64;
+        for (i, &digit) in long.digits.iter().enumerate() {
+            let sum = u64::from(digit) + u64::from(*short.digits.get(i).unwrap_or(&0)) + carry;
+            digits.push(sum as u32);
+            carry = sum >> 32;
+        }
+        digits.push(carry as u32);
+        BigUint { digits }.trim()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    // Long multiplication, which is plenty for numbers of a few hundred digits
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut digits = vec![0u32; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let n = u64::from(a) * u64::from(b) + u64::from(digits[i + j]) + carry;
+                digits[i + j] = n as u32;
+                carry = n >> 32;
+            }
+            digits[i + other.digits.len()] = carry as u32;
+        }
+        BigUint { digits }.trim()
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+
+        // Nine decimal digits at a time, least significant first
+        const CHUNK: u32 = 1_000_000_000;
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_rem_small(CHUNK));
+        }
+        let mut s = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            s.push_str(&format!("{:09}", chunk));
+        }
+        f.pad(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        assert_eq!("0", BigUint::zero().to_string());
+        assert_eq!(
+            "22",
+            BigUint::from_bits([true, false, true, true, false]).to_string()
+        );
+
+        let max = BigUint::from(u128::MAX);
+        assert_eq!(u128::MAX.to_string(), max.to_string());
+        assert_eq!(
+            "115792089237316195423570985008687907852589419931798687112530834793049593217025",
+            (&max * &max).to_string()
+        );
+        assert_eq!(
+            "340282366920938463463374607431768211456",
+            (&max + &BigUint::from(1u64)).to_string()
+        );
+        assert_eq!(
+            BigUint::from(1u64 << 40),
+            BigUint::from_bits((0..41).map(|i| i == 0))
+        );
+    }
+}
@@ -0,0 +1,126 @@
+// Command lines for the modes a day has besides solving the puzzle, like `day_1 --stream`. A mode
+// reads its options from `Args` and returns any error instead of exiting, which the day's binary
+// reports.
+//
+//     let mut args = Args::new(args);
+//     while let Some(arg) = args.next_arg() {
+//         match arg.as_str() {
+//             "--window" | "-w" => size = args.number(&arg, 1)?,
+//             "--filter" | "-f" => filter = args.value(&arg)?.parse()?,
+//             _ => args.path(arg)?,
+//         }
+//     }
+//     let input = args.input(crate::DAY.input)?;
+
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+pub struct Args<I> {
+    args: I,
+    path: Option<String>,
+}
+
+impl<I: Iterator<Item = String>> Args<I> {
+    pub fn new(args: I) -> Self {
+        Self { args, path: None }
+    }
+
+    pub fn next_arg(&mut self) -> Option<String> {
+        self.args.next()
+    }
+
+    /// The argument after `flag`.
+    pub fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.args
+            .next()
+            .ok_or_else(|| format!("{} needs an argument", flag))
+    }
+
+    /// The argument after `flag` as a `T`, described as `what` if it isn't one.
+    pub fn parse<T: FromStr>(&mut self, flag: &str, what: &str) -> Result<T, String> {
+        self.value(flag)?
+            .parse()
+            .map_err(|_| format!("{} needs {}", flag, what))
+    }
+
+    /// The number after `flag`, which has to be at least `min`.
+    pub fn number<T>(&mut self, flag: &str, min: T) -> Result<T, String>
+    where
+        T: FromStr + PartialOrd + fmt::Display,
+    {
+        self.value(flag)?
+            .parse()
+            .ok()
+            .filter(|n| *n >= min)
+            .ok_or_else(|| format!("{} needs a number of at least {}", flag, min))
+    }
+
+    /// Take `arg` as the input file, unless there already is one or it looks like a flag.
+    pub fn path(&mut self, arg: String) -> Result<(), String> {
+        if arg.starts_with('-') || self.path.is_some() {
+            return Err(unexpected(&arg));
+        }
+        self.path = Some(arg);
+        Ok(())
+    }
+
+    /// The input file's contents, or `default` without one.
+    pub fn input(&self, default: &str) -> Result<String, String> {
+        match &self.path {
+            Some(path) => {
+                fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))
+            }
+            None => Ok(default.to_string()),
+        }
+    }
+}
+
+pub fn unexpected(arg: &str) -> String {
+    format!("unexpected argument `{}`", arg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let words = "input.txt --window 3 -w x --max 0.5 --rules --size big other";
+        let mut args = Args::new(words.split(' ').map(String::from));
+
+        let path = args.next_arg().unwrap();
+        args.path(path).unwrap();
+        assert_eq!(Some("--window".to_string()), args.next_arg());
+        assert_eq!(Ok(3), args.number("--window", 1));
+        args.next_arg();
+        assert_eq!(
+            Err("-w needs a number of at least 1".to_string()),
+            args.number::<usize>("-w", 1)
+        );
+        args.next_arg();
+        assert_eq!(Ok(0.5), args.number("--max", 0.0));
+        args.next_arg();
+        assert_eq!(Ok("--size".to_string()), args.value("--rules"));
+        assert_eq!(
+            Err("--size needs a size".to_string()),
+            args.parse::<u32>("--size", "a size")
+        );
+        let other = args.next_arg().unwrap();
+        assert_eq!(
+            Err("unexpected argument `other`".to_string()),
+            args.path(other)
+        );
+        assert_eq!(
+            Err("--rules needs an argument".to_string()),
+            args.value("--rules")
+        );
+
+        let err = args.input("").unwrap_err();
+        assert!(err.starts_with("couldn't read input.txt: "), "{}", err);
+        assert_eq!(
+            Ok("default".to_string()),
+            Args::new(None.into_iter()).input("default")
+        );
+    }
+}
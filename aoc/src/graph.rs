@@ -0,0 +1,319 @@
+// Graph algorithms over anything that can list a node's neighbours.
+//
+// A graph is just a neighbour function: given a node it returns the nodes you can move to and the
+// cost of each move. `Grid`s and adjacency maps implement `Graph` directly, and `from_fn` turns a
+// closure into one for graphs that are only known implicitly.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::grid::Grid;
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes reachable from `node` in one move, and the cost of each move.
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, u64)>;
+}
+
+// Moving onto a cell costs the value of that cell.
+impl<T: Copy + Into<u64>> Graph for Grid<T> {
+    type Node = (usize, usize);
+
+    fn neighbours(&self, &(x, y): &Self::Node) -> Vec<(Self::Node, u64)> {
+        self.adjacent(x, y).map(|p| (p, self[p].into())).collect()
+    }
+}
+
+// Every edge in an adjacency map costs 1.
+impl<N: Clone + Eq + Hash> Graph for HashMap<N, Vec<N>> {
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> Vec<(N, u64)> {
+        self.get(node)
+            .map(|nodes| nodes.iter().map(|n| (n.clone(), 1)).collect())
+            .unwrap_or_default()
+    }
+}
+
+pub struct FromFn<N, F> {
+    neighbours: F,
+    node: PhantomData<N>,
+}
+
+/// A graph defined by a neighbour function.
+pub fn from_fn<N, F>(neighbours: F) -> FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    FromFn {
+        neighbours,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> Vec<(N, u64)> {
+        (self.neighbours)(node)
+    }
+}
+
+// The priority queue depends on `Ord`.
+//
+// Explicitly implement the trait so the queue becomes a min-heap instead of a max-heap. Only the
+// priority (the cost so far plus the estimate of the rest) and cost take part in the comparison so
+// nodes don't need to be `Ord`.
+struct State<N> {
+    priority: u64,
+    cost: u64,
+    node: N,
+}
+
+impl<N> Ord for State<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Notice that the we flip the ordering on costs. Ties go to the node furthest along,
+        // which is more likely to be close to the goal when there's an estimate.
+        other
+            .priority
+            .cmp(&self.priority)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N> PartialOrd for State<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> PartialEq for State<N> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.priority, self.cost) == (other.priority, other.cost)
+    }
+}
+
+impl<N> Eq for State<N> {}
+
+// The lowest cost found so far for each node, and the node we reached it from
+type Best<N> = HashMap<N, (u64, Option<N>)>;
+
+// https://doc.rust-lang.org/std/collections/binary_heap/index.html#examples
+//
+// With an `estimate` of the cost left from each node this is A*. The estimate must never be more
+// than the real cost, and an estimate of 0 is plain Dijkstra.
+fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    mut goal: impl FnMut(&G::Node) -> bool,
+    estimate: impl Fn(&G::Node) -> u64,
+) -> (Best<G::Node>, Option<G::Node>) {
+    let mut best = Best::new();
+    best.insert(start.clone(), (0, None));
+
+    let mut heap = BinaryHeap::new();
+    heap.push(State {
+        priority: estimate(&start),
+        cost: 0,
+        node: start,
+    });
+
+    while let Some(State { cost, node, .. }) = heap.pop() {
+        if cost > best[&node].0 {
+            continue;
+        }
+        if goal(&node) {
+            return (best, Some(node));
+        }
+
+        for (next, step) in graph.neighbours(&node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|&(c, _)| next_cost < c) {
+                // We have now found a better way
+                best.insert(next.clone(), (next_cost, Some(node.clone())));
+                heap.push(State {
+                    priority: next_cost + estimate(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    (best, None)
+}
+
+/// The lowest cost of reaching every node reachable from `start`.
+pub fn distances<G: Graph>(graph: &G, start: G::Node) -> HashMap<G::Node, u64> {
+    let (best, _) = dijkstra(graph, start, |_| false, |_| 0);
+    best.into_iter().map(|(n, (cost, _))| (n, cost)).collect()
+}
+
+/// The lowest cost path from `start` to the first node matching `goal`, as the total cost and
+/// every node along the way (including both ends).
+pub fn shortest_path<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    goal: impl FnMut(&G::Node) -> bool,
+) -> Option<(u64, Vec<G::Node>)> {
+    a_star(graph, start, goal, |_| 0)
+}
+
+/// `shortest_path`, guided by an `estimate` of the cost from each node to the goal. The estimate
+/// must never be more than the real cost.
+pub fn a_star<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    goal: impl FnMut(&G::Node) -> bool,
+    estimate: impl Fn(&G::Node) -> u64,
+) -> Option<(u64, Vec<G::Node>)> {
+    let (best, end) = dijkstra(graph, start, goal, estimate);
+    let end = end?;
+
+    // Walk back from the end to the start
+    let mut path = vec![end.clone()];
+    while let Some(previous) = best[path.last().unwrap()].1.clone() {
+        path.push(previous);
+    }
+    path.reverse();
+
+    Some((best[&end].0, path))
+}
+
+/// Every node reachable from `start` in breadth-first order, with the number of moves (ignoring
+/// costs) needed to reach it.
+pub fn bfs<G: Graph>(graph: &G, start: G::Node) -> Vec<(G::Node, usize)> {
+    let mut visited = HashSet::new();
+    visited.insert(start.clone());
+
+    let mut queue = VecDeque::new();
+    queue.push_back((start, 0));
+
+    let mut order = Vec::new();
+    while let Some((node, steps)) = queue.pop_front() {
+        for (next, _) in graph.neighbours(&node) {
+            if visited.insert(next.clone()) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+        order.push((node, steps));
+    }
+    order
+}
+
+/// Every node reachable from `start` in depth-first (pre-)order.
+pub fn dfs<G: Graph>(graph: &G, start: G::Node) -> Vec<G::Node> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+
+    let mut order = Vec::new();
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        // Push in reverse so neighbours are visited in the order they're listed
+        for (next, _) in graph.neighbours(&node).into_iter().rev() {
+            if !visited.contains(&next) {
+                stack.push(next);
+            }
+        }
+        order.push(node);
+    }
+    order
+}
+
+/// The number of distinct paths from `start` to `goal`. A path only moves on to a node if
+/// `can_visit` allows it given the path so far (which starts with `start`); paths stop as soon as
+/// they reach `goal`.
+pub fn count_paths<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    goal: &G::Node,
+    mut can_visit: impl FnMut(&[G::Node], &G::Node) -> bool,
+) -> usize {
+    fn count<G: Graph>(
+        graph: &G,
+        path: &mut Vec<G::Node>,
+        goal: &G::Node,
+        can_visit: &mut impl FnMut(&[G::Node], &G::Node) -> bool,
+    ) -> usize {
+        let mut paths = 0;
+        for (next, _) in graph.neighbours(path.last().unwrap()) {
+            if &next == goal {
+                paths += 1;
+            } else if can_visit(path, &next) {
+                path.push(next);
+                paths += count(graph, path, goal, can_visit);
+                path.pop();
+            }
+        }
+        paths
+    }
+
+    count(graph, &mut vec![start], goal, &mut can_visit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        #[rustfmt::skip]
+        let grid = Grid::new(3, 3, vec![
+            1, 9, 1,
+            1, 9, 1,
+            1, 1, 1u8,
+        ]);
+
+        let (cost, path) = shortest_path(&grid, (0, 0), |&n| n == (2, 0)).unwrap();
+        assert_eq!(6, cost);
+        assert_eq!(
+            vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0)],
+            path
+        );
+        assert_eq!(6, distances(&grid, (0, 0))[&(2, 0)]);
+
+        // Every cell costs at least 1, so the manhattan distance never overestimates
+        let manhattan = |&(x, y): &(usize, usize)| (2 - x + y) as u64;
+        let (cost, _) = a_star(&grid, (0, 0), |&n| n == (2, 0), manhattan).unwrap();
+        assert_eq!(6, cost);
+
+        let steps: HashMap<_, _> = bfs(&grid, (0, 0)).into_iter().collect();
+        assert_eq!(9, steps.len());
+        assert_eq!(4, steps[&(2, 2)]);
+    }
+
+    #[test]
+    fn adjacency_map() {
+        let mut graph = HashMap::new();
+        graph.insert('a', vec!['b', 'c']);
+        graph.insert('b', vec!['d']);
+        graph.insert('c', vec!['d']);
+        graph.insert('d', vec!['a']);
+
+        assert_eq!(vec!['a', 'b', 'd', 'c'], dfs(&graph, 'a'));
+        assert_eq!(2, count_paths(&graph, 'a', &'d', |_, _| true));
+        assert_eq!(1, count_paths(&graph, 'a', &'d', |_, &n| n != 'c'));
+
+        let (cost, _) = shortest_path(&graph, 'b', |&n| n == 'c').unwrap();
+        assert_eq!(3, cost);
+    }
+
+    #[test]
+    fn implicit() {
+        // Steps of +1 or *2
+        let graph = from_fn(|&n: &u32| vec![(n + 1, 1), (n * 2, 1)]);
+        let (cost, path) = shortest_path(&graph, 1, |&n| n == 10).unwrap();
+        assert_eq!(4, cost);
+        assert_eq!(vec![1, 2, 4, 5, 10], path);
+    }
+}
@@ -0,0 +1,57 @@
+// Shared code for the `day_N` crates.
+
+use std::fmt;
+
+pub mod bigint;
+pub mod cli;
+pub mod graph;
+pub mod grid;
+pub mod params;
+pub mod parse;
+pub mod plugin;
+pub mod runner;
+
+use params::{Param, Params};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Everything the runner needs to know about a day.
+pub struct Day {
+    pub name: &'static str,
+    /// The puzzle input bundled with the day.
+    pub input: &'static str,
+    pub params: &'static [Param],
+    /// The answers for `input` with every parameter left at its default.
+    pub answers: [&'static str; 2],
+    pub solve: fn(&str, &Params) -> Result<Answers>,
+    /// Other ways of solving the day, for comparing against `solve`.
+    pub alternatives: &'static [Alternative],
+}
+
+pub struct Alternative {
+    pub name: &'static str,
+    pub solve: fn(&str, &Params) -> Result<Answers>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: String,
+    pub part_2: String,
+    /// Pictures of the puzzle as it was solved, e.g. the grid after each step. Only drawn when
+    /// `Params::frames` asks for them.
+    pub frames: Vec<String>,
+}
+
+impl Answers {
+    pub fn new(part_1: impl fmt::Display, part_2: impl fmt::Display) -> Self {
+        Self {
+            part_1: part_1.to_string(),
+            part_2: part_2.to_string(),
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn with_frames(self, frames: Vec<String>) -> Self {
+        Self { frames, ..self }
+    }
+}
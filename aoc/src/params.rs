@@ -0,0 +1,195 @@
+// Tunable puzzle parameters.
+//
+// Each day declares its parameters up front with a default, and the runner lets them be overridden
+// from the command line with `--param name=value`. Values are kept as strings and only parsed when
+// the day asks for them, so a day can use whatever type suits it.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            default,
+            description,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Unknown {
+        name: String,
+        known: Vec<&'static str>,
+    },
+    Invalid {
+        name: String,
+        value: String,
+        reason: String,
+    },
+    Malformed(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Unknown { name, known } if known.is_empty() => {
+                write!(f, "unknown parameter `{}`, there are no parameters", name)
+            }
+            Error::Unknown { name, known } => write!(
+                f,
+                "unknown parameter `{}`, expected one of: {}",
+                name,
+                known.join(", ")
+            ),
+            Error::Invalid {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value `{}` for `{}`: {}", value, name, reason),
+            Error::Malformed(s) => write!(f, "expected `name=value`, found `{}`", s),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    declared: &'static [Param],
+    values: Vec<String>,
+    frames: bool,
+}
+
+impl Params {
+    /// The declared parameters, all set to their defaults.
+    pub fn new(declared: &'static [Param]) -> Self {
+        Self {
+            declared,
+            values: declared.iter().map(|p| p.default.to_string()).collect(),
+            frames: false,
+        }
+    }
+
+    /// Ask for frames, which days only draw when someone is going to look at them.
+    pub fn with_frames(self) -> Self {
+        Self {
+            frames: true,
+            ..self
+        }
+    }
+
+    /// Whether to draw frames, see `Answers::frames`.
+    pub fn frames(&self) -> bool {
+        self.frames
+    }
+
+    pub fn declared(&self) -> &'static [Param] {
+        self.declared
+    }
+
+    fn index(&self, name: &str) -> Result<usize, Error> {
+        self.declared
+            .iter()
+            .position(|p| p.name == name)
+            .ok_or_else(|| Error::Unknown {
+                name: name.to_string(),
+                known: self.declared.iter().map(|p| p.name).collect(),
+            })
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        let i = self.index(name)?;
+        self.values[i] = value.to_string();
+        Ok(())
+    }
+
+    /// Apply an override written as `name=value`.
+    pub fn set_from_str(&mut self, s: &str) -> Result<(), Error> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| Error::Malformed(s.to_string()))?;
+        self.set(name.trim(), value.trim())
+    }
+
+    /// The raw value of a parameter.
+    pub fn value(&self, name: &str) -> Result<&str, Error> {
+        Ok(&self.values[self.index(name)?])
+    }
+
+    pub fn get<T>(&self, name: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.value(name)?;
+        value.parse().map_err(|e: T::Err| Error::Invalid {
+            name: name.to_string(),
+            value: value.to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    /// Whether every parameter still has its default value, i.e. the puzzle's own answers apply.
+    pub fn is_default(&self) -> bool {
+        self.declared
+            .iter()
+            .zip(&self.values)
+            .all(|(p, v)| p.default == v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new("days", "80", "Days to simulate"),
+        Param::new("bits", "12", "Bits per number"),
+    ];
+
+    #[test]
+    fn it_works() {
+        let mut params = Params::new(PARAMS);
+        assert!(params.is_default());
+        assert_eq!(Ok(80), params.get::<usize>("days"));
+
+        params.set_from_str("days=1000").unwrap();
+        assert!(!params.is_default());
+        assert_eq!(Ok(1000), params.get::<usize>("days"));
+        assert_eq!(Ok(12), params.get::<u32>("bits"));
+
+        params.set("days", "80").unwrap();
+        assert!(params.is_default());
+
+        assert!(!params.frames());
+        assert!(params.with_frames().frames());
+    }
+
+    #[test]
+    fn errors() {
+        let mut params = Params::new(PARAMS);
+        assert_eq!(
+            "unknown parameter `steps`, expected one of: days, bits",
+            params.set("steps", "1").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "expected `name=value`, found `days`",
+            params.set_from_str("days").unwrap_err().to_string()
+        );
+
+        params.set("bits", "many").unwrap();
+        assert_eq!(
+            "invalid value `many` for `bits`: invalid digit found in string",
+            params.get::<u32>("bits").unwrap_err().to_string()
+        );
+    }
+}
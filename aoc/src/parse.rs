@@ -0,0 +1,430 @@
+// Composable parsers for puzzle inputs.
+//
+// A `Parser` is a cursor over (a slice of) the input. Sub-parsers handed out by `lines`, `sections`
+// and friends keep pointing into the original input, so errors always report the line and column
+// in the whole input rather than in the piece being parsed.
+//
+//     let mut p = Parser::new("0,9 -> 5,9\n8,0 -> 0,8\n");
+//     let segments = p.lines(|l| {
+//         let a = l.list(",", |n| n.integer::<u16>())?;
+//         l.tag(" -> ")?;
+//         let b = l.list(",", |n| n.integer::<u16>())?;
+//         Ok((a, b))
+//     })?;
+
+use std::fmt;
+use std::num::ParseIntError;
+
+use crate::grid::Grid;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+// Written out like `Display` so `parse(input).unwrap()` panics with a readable message.
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub trait Integer: Sized {
+    fn from_str_radix(src: &str, radix: u32) -> std::result::Result<Self, ParseIntError>;
+}
+
+macro_rules! integer {
+    ($($t:ty)*) => {
+        $(
+            impl Integer for $t {
+                fn from_str_radix(src: &str, radix: u32) -> std::result::Result<Self, ParseIntError> {
+                    <$t>::from_str_radix(src, radix)
+                }
+            }
+        )*
+    };
+}
+
+integer!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            end: input.len(),
+        }
+    }
+
+    /// Parse the whole of `input` with `f`, allowing only trailing whitespace to be left over.
+    pub fn parse<T>(input: &'a str, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        Self::new(input).complete(f)
+    }
+
+    fn sub(&self, start: usize, end: usize) -> Self {
+        Self {
+            input: self.input,
+            pos: start,
+            end,
+        }
+    }
+
+    /// The unconsumed input.
+    pub fn remaining(&self) -> &'a str {
+        &self.input[self.pos..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.end
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, pos: usize, message: impl Into<String>) -> Error {
+        let before = &self.input[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+            .chars()
+            .count()
+            + 1;
+        Error {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn found(&self) -> String {
+        self.found_at(self.pos)
+    }
+
+    fn found_at(&self, pos: usize) -> String {
+        match self.input[pos..self.end].lines().next() {
+            Some(rest) if !rest.is_empty() => format!("`{}`", rest),
+            _ => "end of line".to_string(),
+        }
+    }
+
+    /// Run `f` and then require that nothing but whitespace is left.
+    pub fn complete<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let value = f(self)?;
+        let pos = self.pos;
+        self.skip_whitespace();
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self.error_at(pos, format!("unexpected {}", self.found_at(pos))))
+        }
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.remaining().chars().next()
+    }
+
+    pub fn char(&mut self) -> Result<char> {
+        match self.peek() {
+            Some(c) => {
+                self.pos += c.len_utf8();
+                Ok(c)
+            }
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    pub fn tag(&mut self, tag: &str) -> Result<()> {
+        if self.eat(tag) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`, found {}", tag, self.found())))
+        }
+    }
+
+    /// Consume `tag` if it is next, returning whether it was.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        // Not built on `tag`, working out an error's line and column isn't cheap
+        let found = self.remaining().starts_with(tag);
+        if found {
+            self.pos += tag.len();
+        }
+        found
+    }
+
+    pub fn take_while(&mut self, mut pred: impl FnMut(char) -> bool) -> &'a str {
+        let rest = self.remaining();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// A run of at least one ASCII letter.
+    pub fn word(&mut self) -> Result<&'a str> {
+        let start = self.pos;
+        match self.take_while(|c| c.is_ascii_alphabetic()) {
+            "" => Err(self.error_at(start, format!("expected a word, found {}", self.found()))),
+            word => Ok(word),
+        }
+    }
+
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    pub fn integer<T: Integer>(&mut self) -> Result<T> {
+        self.integer_radix(10)
+    }
+
+    /// An optionally signed integer in `radix`.
+    pub fn integer_radix<T: Integer>(&mut self, radix: u32) -> Result<T> {
+        let start = self.pos;
+        if !self.eat("-") {
+            self.eat("+");
+        }
+        if self.take_while(|c| c.is_digit(radix)).is_empty() {
+            let found = self.found();
+            self.pos = start;
+            return Err(self.error(format!("expected an integer, found {}", found)));
+        }
+        let src = &self.input[start..self.pos];
+        T::from_str_radix(src, radix)
+            .map_err(|e| self.error_at(start, format!("invalid integer `{}`: {}", src, e)))
+    }
+
+    /// One or more `item`s separated by `separator`.
+    pub fn list<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// One or more `item`s separated by runs of spaces, ignoring leading spaces. Stops before the
+    /// first word that `item` fails on without consuming anything.
+    pub fn words<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        self.skip_spaces();
+        let mut items = vec![item(self)?];
+        loop {
+            let before = self.pos;
+            self.skip_spaces();
+            if self.pos == before || self.is_empty() {
+                return Ok(items);
+            }
+            let start = self.pos;
+            match item(self) {
+                Ok(value) => items.push(value),
+                Err(_) if self.pos == start => {
+                    self.pos = before;
+                    return Ok(items);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Parse the next line with `f`. Surrounding spaces are ignored and the whole line must be
+    /// consumed.
+    pub fn line<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let rest = self.remaining();
+        let len = rest.find('\n').unwrap_or(rest.len());
+        let mut line = self.sub(self.pos, self.pos + len);
+        self.pos = (self.pos + len + 1).min(self.end);
+
+        line.skip_whitespace();
+        line.complete(f)
+    }
+
+    /// Parse every remaining non-blank line with `f`, see `line`.
+    pub fn lines<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.is_empty() {
+                return Ok(items);
+            }
+            items.push(self.line(&mut f)?);
+        }
+    }
+
+    /// Parse the next block of lines up to a blank line (or the end of the input) with `f`. The
+    /// whole section must be consumed.
+    pub fn section<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let mut end = self.pos;
+        // Advance a line at a time until we find one that's blank
+        while end < self.end {
+            let line_end = self.input[end..self.end]
+                .find('\n')
+                .map_or(self.end, |i| end + i);
+            if self.input[end..line_end].trim().is_empty() {
+                break;
+            }
+            end = (line_end + 1).min(self.end);
+        }
+        self.pos = end;
+        self.sub(start, end).complete(f)
+    }
+
+    /// Parse every remaining section with `f`, see `section`.
+    pub fn sections<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.is_empty() {
+                return Ok(items);
+            }
+            items.push(self.section(&mut f)?);
+        }
+    }
+
+    /// Parse the remaining lines as a rectangular grid, one cell per character.
+    pub fn grid<T>(&mut self, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>> {
+        self.rows(|line| {
+            let mut row = Vec::new();
+            while !line.is_empty() && !line.peek().unwrap().is_whitespace() {
+                let pos = line.pos;
+                let c = line.char()?;
+                row.push(cell(c).ok_or_else(|| line.error_at(pos, format!("unexpected `{}`", c)))?);
+            }
+            Ok(row)
+        })
+    }
+
+    /// Parse the remaining lines as a rectangular grid of space separated `item`s.
+    pub fn word_grid<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Grid<T>> {
+        self.rows(|line| line.words(&mut item))
+    }
+
+    fn rows<T>(&mut self, mut row: impl FnMut(&mut Self) -> Result<Vec<T>>) -> Result<Grid<T>> {
+        let mut width = None;
+        let rows = self.lines(|line| {
+            let start = line.pos;
+            let row = row(line)?;
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(line.error_at(
+                        start,
+                        format!("expected a row of width {}, found {}", width, row.len()),
+                    ))
+                }
+                _ => {}
+            }
+            Ok(row)
+        })?;
+
+        let height = rows.len();
+        if height == 0 {
+            return Err(self.error("expected a grid"));
+        }
+        Ok(Grid::new(
+            width.unwrap(),
+            height,
+            rows.into_iter().flatten().collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = "1,2,3\n\n4 5\n6  7\n\n  89\n  01\n";
+        let mut p = Parser::new(input);
+
+        let numbers = p.section(|s| s.list(",", |n| n.integer::<u8>())).unwrap();
+        assert_eq!(vec![1, 2, 3], numbers);
+
+        let rows = p
+            .section(|s| s.lines(|l| l.words(|w| w.integer::<u8>())))
+            .unwrap();
+        assert_eq!(vec![vec![4, 5], vec![6, 7]], rows);
+
+        let grid = p.grid(|c| c.to_digit(10)).unwrap();
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert_eq!(9, grid[(1, 0)]);
+        assert_eq!(0, grid[(0, 1)]);
+        assert!(p.is_empty());
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(-12, Parser::parse("-12", |p| p.integer::<i32>()).unwrap());
+        assert_eq!(
+            5,
+            Parser::parse("101", |p| p.integer_radix::<u16>(2)).unwrap()
+        );
+        assert_eq!(
+            "line 1, column 1: invalid integer `300`: number too large to fit in target type",
+            Parser::parse("300", |p| p.integer::<u8>())
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = Parser::parse("1 -> 2\n3 => 4\n", |p| {
+            p.lines(|l| {
+                let a = l.integer::<u8>()?;
+                l.tag(" -> ")?;
+                Ok((a, l.integer::<u8>()?))
+            })
+        })
+        .unwrap_err();
+        assert_eq!(
+            "line 2, column 2: expected ` -> `, found ` => 4`",
+            err.to_string()
+        );
+
+        let err = Parser::parse("12\n345\n", |p| p.grid(|c| c.to_digit(10))).unwrap_err();
+        assert_eq!(
+            "line 2, column 1: expected a row of width 2, found 3",
+            err.to_string()
+        );
+
+        let err = Parser::parse("12\n3x\n", |p| p.grid(|c| c.to_digit(10))).unwrap_err();
+        assert_eq!("line 2, column 2: unexpected `x`", err.to_string());
+
+        let err = Parser::parse("1,2 3", |p| p.list(",", |n| n.integer::<u8>())).unwrap_err();
+        assert_eq!("line 1, column 4: unexpected ` 3`", err.to_string());
+    }
+}
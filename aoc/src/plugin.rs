@@ -0,0 +1,256 @@
+// The C ABI for solvers built as dynamic libraries, so the runner can load alternative solutions
+// from other crates.
+//
+// A plugin exports two functions:
+//
+//     uint32_t aoc_plugin_abi_version(void);
+//     const Descriptor *aoc_plugin(void);
+//
+// The version is checked before anything else is touched, so everything past it is free to change
+// as long as `ABI_VERSION` changes with it. Rust plugins are `cdylib` crates that wrap a `Day`:
+//
+//     const DAY: Day = Day { solve, ..day_1::DAY };
+//     aoc::export_plugin!(DAY, "day_1_windows");
+
+use std::panic::{self, AssertUnwindSafe};
+use std::{slice, str};
+
+use crate::params::Params;
+use crate::{Answers, Day};
+
+pub const ABI_VERSION: u32 = 1;
+
+pub const VERSION_SYMBOL: &str = "aoc_plugin_abi_version";
+pub const DESCRIPTOR_SYMBOL: &str = "aoc_plugin";
+
+pub type VersionFn = unsafe extern "C" fn() -> u32;
+pub type DescriptorFn = unsafe extern "C" fn() -> *const Descriptor;
+
+/// Borrowed UTF-8 text.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct Str {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+impl Str {
+    pub const fn new(s: &str) -> Self {
+        Self {
+            ptr: s.as_ptr(),
+            len: s.len(),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` bytes that outlive `'a`.
+    pub unsafe fn as_str<'a>(self) -> Result<&'a str, str::Utf8Error> {
+        if self.len == 0 {
+            return Ok("");
+        }
+        str::from_utf8(slice::from_raw_parts(self.ptr, self.len))
+    }
+}
+
+/// Text allocated by the plugin. Only the plugin can free it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct OwnedStr {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl OwnedStr {
+    pub fn new(s: String) -> Self {
+        let mut bytes = std::mem::ManuallyDrop::new(s.into_bytes());
+        Self {
+            ptr: bytes.as_mut_ptr(),
+            len: bytes.len(),
+            capacity: bytes.capacity(),
+        }
+    }
+
+    /// A copy of the text.
+    ///
+    /// # Safety
+    ///
+    /// `self` must come from `OwnedStr::new` and not have been freed.
+    pub unsafe fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(slice::from_raw_parts(self.ptr, self.len)).into_owned()
+    }
+
+    /// # Safety
+    ///
+    /// `self` must come from `OwnedStr::new` in this same library.
+    unsafe fn free(self) {
+        drop(String::from_raw_parts(self.ptr, self.len, self.capacity));
+    }
+}
+
+/// What `solve` returns: both answers, or why there aren't any.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Output {
+    pub ok: bool,
+    pub part_1: OwnedStr,
+    pub part_2: OwnedStr,
+    pub error: OwnedStr,
+}
+
+impl Output {
+    fn new(result: crate::Result<Answers>) -> Self {
+        let (ok, part_1, part_2, error) = match result {
+            Ok(answers) => (true, answers.part_1, answers.part_2, String::new()),
+            Err(e) => (false, String::new(), String::new(), e.to_string()),
+        };
+        Self {
+            ok,
+            part_1: OwnedStr::new(part_1),
+            part_2: OwnedStr::new(part_2),
+            error: OwnedStr::new(error),
+        }
+    }
+}
+
+#[repr(C)]
+pub struct Descriptor {
+    /// The name of the day this solves, e.g. `day_1`. The plugin shares its input, parameters and
+    /// answers.
+    pub day: Str,
+    /// What to call the plugin, to tell it apart from the day itself.
+    pub name: Str,
+    /// Solve `input`, with any parameters given as `name=value` lines.
+    pub solve: unsafe extern "C" fn(input: Str, params: Str) -> Output,
+    /// Free an `Output` returned by `solve`.
+    pub free: unsafe extern "C" fn(output: Output),
+}
+
+// Descriptors only point at static data
+unsafe impl Sync for Descriptor {}
+
+/// The `solve` of a plugin wrapping `day`. Panics are caught here as they can't unwind into the
+/// runner.
+///
+/// # Safety
+///
+/// `input` and `params` must be valid for the duration of the call.
+pub unsafe fn solve(day: &Day, input: Str, params: Str) -> Output {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let input = input.as_str()?;
+        let mut values = Params::new(day.params);
+        for param in params.as_str()?.lines() {
+            values.set_from_str(param)?;
+        }
+        (day.solve)(input, &values)
+    }));
+    Output::new(result.unwrap_or_else(|_| Err("panicked".into())))
+}
+
+/// The `free` of a plugin.
+///
+/// # Safety
+///
+/// `output` must come from `solve` in this same library.
+pub unsafe fn free(output: Output) {
+    output.part_1.free();
+    output.part_2.free();
+    output.error.free();
+}
+
+/// Export `$day` from a `cdylib` crate as a plugin called `$name`.
+#[macro_export]
+macro_rules! export_plugin {
+    ($day:path, $name:expr) => {
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_abi_version() -> u32 {
+            $crate::plugin::ABI_VERSION
+        }
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin() -> *const $crate::plugin::Descriptor {
+            unsafe extern "C" fn solve(
+                input: $crate::plugin::Str,
+                params: $crate::plugin::Str,
+            ) -> $crate::plugin::Output {
+                $crate::plugin::solve(&$day, input, params)
+            }
+
+            unsafe extern "C" fn free(output: $crate::plugin::Output) {
+                $crate::plugin::free(output)
+            }
+
+            static DESCRIPTOR: $crate::plugin::Descriptor = $crate::plugin::Descriptor {
+                day: $crate::plugin::Str::new($day.name),
+                name: $crate::plugin::Str::new($name),
+                solve,
+                free,
+            };
+            &DESCRIPTOR
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Param;
+
+    const DAY: Day = Day {
+        name: "day_0",
+        input: "",
+        params: &[Param::new(
+            "times",
+            "2",
+            "How many times to repeat the input",
+        )],
+        answers: ["", ""],
+        solve: |input, params| {
+            let times = params.get("times")?;
+            if times == 0 {
+                panic!("nothing to repeat");
+            }
+            Ok(Answers::new(input.repeat(times), input.len()))
+        },
+        alternatives: &[],
+    };
+
+    crate::export_plugin!(DAY, "day_0_test");
+
+    // Call the plugin the way the runner would
+    fn call(input: &str, params: &str) -> Result<(String, String), String> {
+        unsafe {
+            assert_eq!(ABI_VERSION, aoc_plugin_abi_version());
+            let descriptor = &*aoc_plugin();
+            assert_eq!("day_0", descriptor.day.as_str().unwrap());
+            assert_eq!("day_0_test", descriptor.name.as_str().unwrap());
+
+            let output = (descriptor.solve)(Str::new(input), Str::new(params));
+            let result = if output.ok {
+                Ok((
+                    output.part_1.to_string_lossy(),
+                    output.part_2.to_string_lossy(),
+                ))
+            } else {
+                Err(output.error.to_string_lossy())
+            };
+            (descriptor.free)(output);
+            result
+        }
+    }
+
+    #[test]
+    fn it_works() {
+        assert_eq!(Ok(("abab".to_string(), "2".to_string())), call("ab", ""));
+        assert_eq!(
+            Ok(("ababab".to_string(), "2".to_string())),
+            call("ab", "times=3")
+        );
+        assert_eq!(
+            Err("invalid value `x` for `times`: invalid digit found in string".to_string()),
+            call("ab", "times=x")
+        );
+        assert_eq!(Err("panicked".to_string()), call("ab", "times=0"));
+    }
+}
@@ -0,0 +1,116 @@
+// The command line entry point shared by every day.
+//
+//     cargo run --bin day_6                           # solve and check the answers
+//     cargo run --bin day_6 -- --param days=1000      # "what if" with different parameters
+//     cargo run --bin day_6 -- --help                 # list the parameters
+//     PRINT=1 cargo run --bin day_11                  # animate the day's frames
+
+use std::{env, process, thread, time};
+
+use crate::params::Params;
+use crate::{Answers, Day};
+
+pub fn main(day: &Day) {
+    let mut params = Params::new(day.params);
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let result = match arg.as_str() {
+            "--param" | "-p" => match args.next() {
+                Some(param) => params.set_from_str(&param),
+                None => exit(day, "--param needs a `name=value` argument"),
+            },
+            "--help" | "-h" => {
+                usage(day);
+                return;
+            }
+            _ => exit(day, &format!("unexpected argument `{}`", arg)),
+        };
+        if let Err(e) = result {
+            exit(day, &e.to_string());
+        }
+    }
+
+    if env::var("PRINT").is_ok() {
+        params = params.with_frames();
+    }
+    let answers = match (day.solve)(day.input, &params) {
+        Ok(answers) => answers,
+        Err(e) => exit(day, &e.to_string()),
+    };
+
+    if env::var("PRINT").is_ok() {
+        animate(&answers.frames);
+    }
+
+    print_answer(1, &answers.part_1);
+    print_answer(2, &answers.part_2);
+
+    // The known answers only hold for the default parameters
+    if params.is_default() {
+        if let Err(e) = verify(day, &answers) {
+            exit(day, &e);
+        }
+    }
+}
+
+/// Check `answers` against the day's known answers.
+pub fn verify(day: &Day, answers: &Answers) -> Result<(), String> {
+    for (part, (expected, actual)) in day
+        .answers
+        .iter()
+        .zip(&[&answers.part_1, &answers.part_2])
+        .enumerate()
+    {
+        if expected != actual {
+            return Err(format!(
+                "part {} is {}, expected {}",
+                part + 1,
+                actual,
+                expected
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn animate(frames: &[String]) {
+    for frame in frames {
+        thread::sleep(time::Duration::from_millis(100));
+        print!("\x1b[2J"); // clear
+        print!("\x1b[{};{}H", 1, 1); // move cursor
+        print!("{}", frame);
+    }
+}
+
+/// Print one part's answer. Answers drawn as pictures go on their own lines.
+pub fn print_answer(part: usize, answer: &str) {
+    if answer.contains('\n') {
+        println!("Part {}:\n{}", part, answer);
+    } else {
+        println!("Part {}: {}", part, answer);
+    }
+}
+
+fn usage(day: &Day) {
+    println!("Usage: {} [--param NAME=VALUE]...", day.name);
+    if day.params.is_empty() {
+        return;
+    }
+    println!("\nParameters:");
+    let width = day.params.iter().map(|p| p.name.len()).max().unwrap();
+    for p in day.params {
+        println!(
+            "    {:width$}  {} (default: {})",
+            p.name,
+            p.description,
+            p.default,
+            width = width
+        );
+    }
+}
+
+fn exit(day: &Day, message: &str) -> ! {
+    eprintln!("{}: {}", day.name, message);
+    process::exit(1);
+}
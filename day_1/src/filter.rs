@@ -0,0 +1,193 @@
+// Smoothing noisy depth readings, and picking out the readings that don't fit in.
+//
+//     cargo run --bin day_1 -- --anomalies
+//     cargo run --bin day_1 -- --anomalies sonar.log --filter mean --window 9 --threshold 25
+//     cargo run --bin day_1 -- --anomalies --sums 3
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::cli::Args;
+use aoc::parse::Parser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    Median,
+    Mean,
+}
+
+impl Filter {
+    /// Each depth replaced by the median or mean of the `size` depths centred on it. Windows are
+    /// cut short at the ends.
+    pub fn apply(self, depths: &[i32], size: usize) -> Vec<f64> {
+        assert!(size > 0, "a window needs at least one depth");
+        let windows = (0..depths.len()).map(|i| {
+            let start = i.saturating_sub(size / 2);
+            let end = (i + size - size / 2).min(depths.len());
+            start..end
+        });
+
+        match self {
+            Filter::Median => windows
+                .map(|window| {
+                    let mut window = depths[window].to_vec();
+                    window.sort_unstable();
+                    let middle = window.len() / 2;
+                    if window.len() % 2 == 1 {
+                        f64::from(window[middle])
+                    } else {
+                        (f64::from(window[middle - 1]) + f64::from(window[middle])) / 2.0
+                    }
+                })
+                .collect(),
+            Filter::Mean => {
+                // Sums from prefix sums so windows with equal sums get exactly equal means
+                let mut prefix = vec![0i64];
+                for &depth in depths {
+                    prefix.push(prefix.last().unwrap() + i64::from(depth));
+                }
+                windows
+                    .map(|w| (prefix[w.end] - prefix[w.start]) as f64 / w.len() as f64)
+                    .collect()
+            }
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "median" => Ok(Filter::Median),
+            "mean" => Ok(Filter::Mean),
+            _ => Err(format!("unknown filter `{}`, expected median or mean", s)),
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Median => write!(f, "median"),
+            Filter::Mean => write!(f, "mean"),
+        }
+    }
+}
+
+/// The index of the last value of every window of `size` values in a (smoothed) series that sums
+/// to more than the window before it, like `increases`. Neighbouring windows share all but their
+/// ends, so comparing the ends is enough.
+pub fn rises(series: &[f64], size: usize) -> Vec<usize> {
+    assert!(size > 0, "a window needs at least one depth");
+    (size..series.len())
+        .filter(|&i| series[i] > series[i - size])
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub index: usize,
+    pub depth: i32,
+    /// What the filter made of the depths around it.
+    pub expected: f64,
+}
+
+/// The depths more than `threshold` away from the filtered value at the same index.
+pub fn anomalies(depths: &[i32], filter: Filter, size: usize, threshold: f64) -> Vec<Anomaly> {
+    filter
+        .apply(depths, size)
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, expected)| (f64::from(depths[i]) - expected).abs() > threshold)
+        .map(|(index, expected)| Anomaly {
+            index,
+            depth: depths[index],
+            expected,
+        })
+        .collect()
+}
+
+/// `day_1 --anomalies`, reading the bundled input unless given a file. The increases before and
+/// after filtering compare single depths, or the sums of `--sums` of them.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut filter = Filter::Median;
+    let mut size = 5;
+    let mut threshold = 50.0;
+    let mut sums = 1;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--filter" | "-f" => filter = args.value(&arg)?.parse()?,
+            "--window" | "-w" => size = args.number(&arg, 1)?,
+            "--threshold" | "-t" => threshold = args.number(&arg, 0.0)?,
+            "--sums" | "-s" => sums = args.number(&arg, 1)?,
+            _ => args.path(arg)?,
+        }
+    }
+
+    let input = args.input(crate::DAY.input)?;
+    let depths = Parser::parse(&input, |p| p.lines(|l| l.integer::<i32>()))?;
+
+    let anomalies = anomalies(&depths, filter, size, threshold);
+    println!(
+        "Depths more than {} from the {} of the {} around them:",
+        threshold, filter, size
+    );
+    for a in &anomalies {
+        println!(
+            "    {:>8}: {} (expected {:.1})",
+            a.index, a.depth, a.expected
+        );
+    }
+    println!("{} anomalies in {} depths", anomalies.len(), depths.len());
+
+    let raw = crate::increases(&depths, sums).len();
+    let smoothed = rises(&filter.apply(&depths, size), sums).len();
+    println!(
+        "Increases in sums of {}: {} raw, {} after the {} filter",
+        sums, raw, smoothed, filter
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let depths = [200, 201, 900, 203, 204, 205, 100, 207];
+        assert_eq!(
+            vec![201.0, 202.0, 203.0, 204.0, 204.0, 204.0, 204.5, 205.0],
+            Filter::Median.apply(&depths, 5)
+        );
+        assert_eq!(
+            vec![200.0, 201.0, 900.0, 203.0],
+            Filter::Mean.apply(&depths[..4], 1)
+        );
+        assert_eq!(vec![200.5, 200.5], Filter::Mean.apply(&depths[..2], 3));
+
+        let found: Vec<_> = anomalies(&depths, Filter::Median, 5, 50.0)
+            .into_iter()
+            .map(|a| (a.index, a.depth))
+            .collect();
+        assert_eq!(vec![(2, 900), (6, 100)], found);
+
+        // Smoothing takes out the spike and the dip
+        assert_eq!(vec![1, 2, 4, 5, 7], crate::increases(&depths, 1));
+        assert_eq!(
+            vec![1, 2, 3, 6, 7],
+            rises(&Filter::Median.apply(&depths, 5), 1)
+        );
+
+        // Sums of three, which agree with `increases` on the raw depths
+        let raw: Vec<_> = depths.iter().map(|&d| f64::from(d)).collect();
+        assert_eq!(crate::increases(&depths, 3), rises(&raw, 3));
+        assert_eq!(
+            vec![3, 4, 5, 6, 7],
+            rises(&Filter::Median.apply(&depths, 5), 3)
+        );
+    }
+}
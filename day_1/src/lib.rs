@@ -0,0 +1,101 @@
+// https://adventofcode.com/2021/day/1
+
+use std::collections::VecDeque;
+
+use aoc::params::{Param, Params};
+use aoc::parse::Parser;
+use aoc::{Answers, Day};
+
+pub mod filter;
+pub mod stream;
+
+pub const DAY: Day = Day {
+    name: "day_1",
+    input: include_str!("../input"),
+    params: &[
+        Param::new("part_1_window", "1", "Depths summed per window in part 1"),
+        Param::new("part_2_window", "3", "Depths summed per window in part 2"),
+    ],
+    answers: ["1228", "1257"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let depths = Parser::parse(input, |p| p.lines(|l| l.integer::<i32>()))?;
+
+    let count = |param| -> aoc::Result<usize> {
+        let size = params.get(param)?;
+        if size == 0 {
+            return Err(format!("{} must be at least 1", param).into());
+        }
+        Ok(increases(&depths, size).len())
+    };
+
+    Ok(Answers::new(
+        count("part_1_window")?,
+        count("part_2_window")?,
+    ))
+}
+
+/// The sums of every `size` consecutive depths, compared with the sum before as each depth comes
+/// in. Only the depths in the current window are kept.
+pub struct Window {
+    size: usize,
+    depths: VecDeque<i32>,
+    sum: i64,
+    last_sum: Option<i64>,
+}
+
+impl Window {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "a window needs at least one depth");
+        Self {
+            size,
+            depths: VecDeque::with_capacity(size + 1),
+            sum: 0,
+            last_sum: None,
+        }
+    }
+
+    /// Add the next depth, returning whether the window ending with it sums to more than the
+    /// window before.
+    pub fn push(&mut self, depth: i32) -> bool {
+        // Slide the window along rather than summing it again
+        self.depths.push_back(depth);
+        self.sum += i64::from(depth);
+        if self.depths.len() > self.size {
+            self.sum -= i64::from(self.depths.pop_front().unwrap());
+        }
+        if self.depths.len() < self.size {
+            return false;
+        }
+
+        let increased = self.last_sum.is_some_and(|last| self.sum > last);
+        self.last_sum = Some(self.sum);
+        increased
+    }
+}
+
+/// The index of the last depth of every window of `size` depths that sums to more than the window
+/// before it.
+pub fn increases(depths: &[i32], size: usize) -> Vec<usize> {
+    let mut window = Window::new(size);
+    (0..depths.len())
+        .filter(|&i| window.push(depths[i]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(vec![1, 2, 3, 5, 6, 7, 9], increases(&depths, 1));
+        assert_eq!(vec![3, 6, 7, 8, 9], increases(&depths, 3));
+        assert_eq!(vec![9], increases(&depths, 9));
+        assert!(increases(&depths, 10).is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+// Counting increases in a log of depths too big to hold in memory, read a line at a time. Only
+// the depths in each window are kept, so memory depends on the window sizes and not the log.
+//
+//     cat sonar.log | cargo run --release --bin day_1 -- --stream --window 1 --window 50
+//     cat sonar.log | cargo run --release --bin day_1 -- --stream --every 1000
+
+use std::io::{self, BufRead, Write};
+
+use aoc::cli::{self, Args};
+use aoc::parse::Parser;
+
+use crate::Window;
+
+/// How many depths go by between the counts so far, unless `--every` says otherwise.
+pub const EVERY: u64 = 1_000_000;
+
+pub struct Counter {
+    windows: Vec<(usize, Window)>,
+    counts: Vec<u64>,
+    depths: u64,
+}
+
+impl Counter {
+    pub fn new(sizes: &[usize]) -> Self {
+        Self {
+            windows: sizes
+                .iter()
+                .map(|&size| (size, Window::new(size)))
+                .collect(),
+            counts: vec![0; sizes.len()],
+            depths: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: i32) {
+        self.depths += 1;
+        for ((_, window), count) in self.windows.iter_mut().zip(&mut self.counts) {
+            if window.push(depth) {
+                *count += 1;
+            }
+        }
+    }
+
+    /// How many depths have been pushed.
+    pub fn depths(&self) -> u64 {
+        self.depths
+    }
+
+    /// Each window size with the number of increases so far.
+    pub fn counts(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.windows
+            .iter()
+            .map(|&(size, _)| size)
+            .zip(self.counts.iter().copied())
+    }
+
+    fn summary(&self) -> String {
+        let counts: Vec<_> = self
+            .counts()
+            .map(|(size, count)| format!("window {}: {}", size, count))
+            .collect();
+        format!("{} depths, {}", self.depths, counts.join(", "))
+    }
+}
+
+/// Count the increases in the depths in `input`, one per line, writing the counts so far to
+/// `output` after every `every` depths.
+pub fn stream(
+    mut input: impl BufRead,
+    mut output: impl Write,
+    sizes: &[usize],
+    every: u64,
+) -> aoc::Result<Counter> {
+    let mut counter = Counter::new(sizes);
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(counter);
+        }
+        number += 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let depth = Parser::parse(&line, |p| {
+            p.skip_whitespace();
+            p.integer()
+        })
+        .map_err(|mut e| {
+            e.line = number;
+            e
+        })?;
+        counter.push(depth);
+
+        if counter.depths().is_multiple_of(every) {
+            writeln!(output, "{}", counter.summary())?;
+        }
+    }
+}
+
+/// `day_1 --stream`, reading from stdin.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut sizes = Vec::new();
+    let mut every = EVERY;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--window" | "-w" => sizes.push(args.number(&arg, 1)?),
+            "--every" | "-e" => every = args.number(&arg, 1)?,
+            _ => return Err(cli::unexpected(&arg).into()),
+        }
+    }
+    if sizes.is_empty() {
+        sizes = vec![1, 3];
+    }
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let counter = stream(stdin.lock(), stdout.lock(), &sizes, every)?;
+    println!("Depths: {}", counter.depths());
+    for (size, count) in counter.counts() {
+        println!("Window {}: {} increases", size, count);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = "199\n200\n208\n210\n\n200\n207\n240\n269\n260\n263\n";
+        let mut output = Vec::new();
+        let counter = stream(input.as_bytes(), &mut output, &[1, 3], 4).unwrap();
+
+        assert_eq!(10, counter.depths());
+        assert_eq!(vec![(1, 7), (3, 5)], counter.counts().collect::<Vec<_>>());
+        assert_eq!(
+            "4 depths, window 1: 3, window 3: 1\n8 depths, window 1: 6, window 3: 3\n",
+            String::from_utf8(output).unwrap()
+        );
+
+        let error = stream("1\n2\n\nx\n".as_bytes(), io::sink(), &[1], 1).err();
+        assert_eq!(
+            "line 4, column 1: expected an integer, found `x`",
+            error.unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use aoc::params::Params;
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+
+pub const DAY: Day = Day {
+    name: "day_10",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["374061", "2116639949"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let input = parse(input)?;
+
+    let part_1 = part_1(&input);
+    let part_2 = part_2(&input);
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+enum Line {
+    Corrupt(char),
+    Incomplete(Vec<char>),
+}
+
+impl Line {
+    fn parse(line: &[char]) -> Self {
+        let mut stack = Vec::new();
+        for c in line {
+            match c {
+                '(' | '[' | '{' | '<' => stack.push(*c),
+                ')' => {
+                    if stack.pop().unwrap() != '(' {
+                        return Line::Corrupt(*c);
+                    }
+                }
+                ']' => {
+                    if stack.pop().unwrap() != '[' {
+                        return Line::Corrupt(*c);
+                    }
+                }
+                '}' => {
+                    if stack.pop().unwrap() != '{' {
+                        return Line::Corrupt(*c);
+                    }
+                }
+                '>' => {
+                    if stack.pop().unwrap() != '<' {
+                        return Line::Corrupt(*c);
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+        Line::Incomplete(stack)
+    }
+}
+
+fn part_1(program: &[Vec<char>]) -> u32 {
+    let mut acc = 0;
+    for line in program {
+        match Line::parse(line) {
+            Line::Corrupt(')') => acc += 3,
+            Line::Corrupt(']') => acc += 57,
+            Line::Corrupt('}') => acc += 1197,
+            Line::Corrupt('>') => acc += 25137,
+            Line::Incomplete(_) => {}
+            _ => unreachable!(),
+        }
+    }
+    acc
+}
+
+fn part_2(program: &[Vec<char>]) -> u64 {
+    let mut scores = Vec::new();
+    for line in program {
+        match Line::parse(line) {
+            Line::Corrupt(_) => {}
+            Line::Incomplete(line) => {
+                // For each character, multiply the total score by 5 and then increase the total
+                // score by the point value given for the character.
+                let mut score = 0;
+                for c in line.iter().rev() {
+                    score *= 5;
+                    score += match c {
+                        '(' => 1,
+                        '[' => 2,
+                        '{' => 3,
+                        '<' => 4,
+                        _ => unreachable!(),
+                    };
+                }
+                scores.push(score);
+            }
+        }
+    }
+    scores.sort_unstable();
+    scores[scores.len() / 2]
+}
+
+fn parse(input: &str) -> parse::Result<Vec<Vec<char>>> {
+    Parser::parse(input, |p| {
+        p.lines(|l| Ok(l.take_while(|c| "()[]{}<>".contains(c)).chars().collect()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let example = parse(
+            "[({(<(())[]>[[{[]{<()<>>
+             [(()[<>])]({[<{<<[]>>(
+             {([(<{}[<>[]}>{[]{[(<()>
+             (((({<>}<{<{<>}{[]{[]{}
+             [[<[([]))<([[{}[[()]]]
+             [{[{({}]{}}([{[{{{}}([]
+             {<[[]]>}<{[{[{[]{()[[[]
+             [<(<(<(<{}))><([]([]()
+             <{([([[(<>()){}]>(<<{{
+             <{([{{}}[<[[[<>{}]]]>[]]",
+        )
+        .unwrap();
+
+        assert_eq!(26397, part_1(&example));
+        assert_eq!(288957, part_2(&example));
+    }
+}
@@ -0,0 +1,171 @@
+use aoc::grid;
+use aoc::params::{Param, Params};
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+use std::fmt;
+
+pub const DAY: Day = Day {
+    name: "day_11",
+    input: include_str!("../input"),
+    params: &[
+        Param::new("steps", "100", "Steps to count flashes over for part 1"),
+        Param::new(
+            "max_steps",
+            "100000",
+            "Steps to give up after if the octopuses haven't synchronized",
+        ),
+    ],
+    answers: ["1705", "265"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let mut grid = parse(input)?;
+    let steps: usize = params.get("steps")?;
+    let max_steps: usize = params.get("max_steps")?;
+
+    let mut part_1 = 0;
+    let mut part_2 = None;
+    let mut frames = Vec::new();
+
+    // Keep going until we've counted enough steps and seen the octopuses synchronize
+    let mut step = 0;
+    while step < steps || part_2.is_none() {
+        // Some grids never synchronize
+        if step == max_steps {
+            return Err(format!(
+                "gave up after {} steps, raise max_steps to go further",
+                max_steps
+            )
+            .into());
+        }
+        step += 1;
+        let flashes = grid.step();
+        if params.frames() {
+            frames.push(format!("{:?}", grid));
+        }
+
+        if step <= steps {
+            part_1 += flashes;
+        }
+        if part_2.is_none() && grid.synchronized() {
+            part_2 = Some(step);
+        }
+    }
+
+    Ok(Answers::new(part_1, part_2.unwrap()).with_frames(frames))
+}
+
+fn parse(input: &str) -> parse::Result<Grid> {
+    Parser::parse(input, |p| p.grid(|c| c.to_digit(10).map(|d| d as u8))).map(Grid::new)
+}
+
+#[derive(PartialEq)]
+struct Grid {
+    octopuses: grid::Grid<u8>,
+}
+
+impl fmt::Debug for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.octopuses)
+    }
+}
+
+impl Grid {
+    fn new(octopuses: grid::Grid<u8>) -> Self {
+        Self { octopuses }
+    }
+
+    fn step(&mut self) -> u32 {
+        // First, the energy level of each octopus increases by 1.
+        for o in self.octopuses.iter_mut() {
+            *o += 1;
+        }
+
+        let mut part_1 = 0;
+
+        // Then, any octopus with an energy level greater than 9 flashes. This increases the energy
+        // level of all adjacent octopuses by 1, including octopuses that are diagonally adjacent.
+        // If this causes an octopus to have an energy level greater than 9, it also flashes. This
+        // process continues as long as new octopuses keep having their energy level increased
+        // beyond 9. (An octopus can only flash at most once per step.)
+        loop {
+            let mut flashed = false;
+            for (x, y) in self.octopuses.positions() {
+                if let Some(flashes) = self.try_flash(x, y) {
+                    part_1 += flashes;
+                    if flashes > 1 {
+                        flashed = true;
+                    }
+                }
+            }
+            if !flashed {
+                break;
+            }
+        }
+
+        // Finally, any octopus that flashed during this step has its energy level set to 0, as it
+        // used all of its energy to flash.
+        for o in self.octopuses.iter_mut() {
+            if *o > 9 {
+                *o = 0;
+            }
+        }
+
+        part_1
+    }
+
+    fn try_flash(&mut self, x: usize, y: usize) -> Option<u32> {
+        if self.octopuses[(x, y)] <= 9 || self.octopuses[(x, y)] == u8::MAX {
+            // Not ready to flash OR already flashed
+            return None;
+        }
+        // Set value to MAX to mark it as flashed
+        self.octopuses[(x, y)] = u8::MAX;
+
+        let mut flashes = 0;
+        let adjacent: Vec<_> = self.octopuses.adjacent_diagonal(x, y).collect();
+        for (x, y) in adjacent {
+            self.octopuses[(x, y)] = self.octopuses[(x, y)].saturating_add(1);
+            if let Some(adjacent_flashes) = self.try_flash(x, y) {
+                flashes += adjacent_flashes;
+            }
+        }
+        Some(flashes + 1)
+    }
+
+    fn synchronized(&self) -> bool {
+        self.octopuses.iter().all(|o| o == &0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let example = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+";
+        let mut params = Params::new(DAY.params);
+        let answers = solve(example, &params).unwrap();
+        assert_eq!(("1656", "195"), (&*answers.part_1, &*answers.part_2));
+        assert!(answers.frames.is_empty());
+
+        params.set("max_steps", "194").unwrap();
+        assert_eq!(
+            "gave up after 194 steps, raise max_steps to go further",
+            solve(example, &params).unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use aoc::graph;
+use aoc::params::Params;
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+use std::collections::HashMap;
+
+pub const DAY: Day = Day {
+    name: "day_12",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["4773", "116985"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let graph = Graph::new(input)?;
+
+    let part_1 = graph.distinct_paths(false);
+    let part_2 = graph.distinct_paths(true);
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Ord, PartialOrd)]
+enum Cave<'a> {
+    Start,
+    End,
+    Small(&'a str),
+    Big(&'a str),
+}
+
+#[derive(Debug, Default)]
+struct Graph<'a> {
+    graph: HashMap<Cave<'a>, Vec<Cave<'a>>>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(input: &'a str) -> parse::Result<Self> {
+        fn cave<'a>(p: &mut Parser<'a>) -> parse::Result<Cave<'a>> {
+            let name = p.word()?;
+            Ok(if name == "start" {
+                Cave::Start
+            } else if name == "end" {
+                Cave::End
+            } else if name.chars().next().unwrap().is_ascii_uppercase() {
+                Cave::Big(name)
+            } else {
+                Cave::Small(name)
+            })
+        }
+
+        let edges = Parser::parse(input, |p| {
+            p.lines(|l| {
+                let u = cave(l)?;
+                l.tag("-")?;
+                Ok((u, cave(l)?))
+            })
+        })?;
+
+        let mut graph = Graph::default();
+
+        for (u, v) in edges {
+            graph.add_edge(u, v)
+        }
+
+        Ok(graph)
+    }
+
+    // Add edges pointing both directions
+    fn add_edge(&mut self, u: Cave<'a>, v: Cave<'a>) {
+        self.graph.entry(u.clone()).or_default().push(v.clone());
+        self.graph.entry(v).or_default().push(u);
+    }
+
+    // Your goal is to find the number of distinct paths that start at start, end at end, and don't
+    // visit small caves more than once. There are two types of caves: big caves (written in
+    // uppercase, like A) and small caves (written in lowercase, like b). It would be a waste of
+    // time to visit any small cave more than once, but big caves are large enough that it might be
+    // worth visiting them multiple times. So, all paths you find should visit small caves at most
+    // once, and can visit big caves any number of times.
+    fn distinct_paths(&self, allow_double_visit_one_small_cave: bool) -> usize {
+        graph::count_paths(
+            &self.graph,
+            Cave::Start,
+            &Cave::End,
+            |path, cave| match cave {
+                // Start can only be visited once, this is a dead end.
+                Cave::Start => false,
+                // Reaching the end finishes the path, so we never get asked about it
+                Cave::End => unreachable!(),
+                // Allowed to visit more than once
+                Cave::Big(_name) => true,
+                Cave::Small(_name) => {
+                    if allow_double_visit_one_small_cave {
+                        // A single small cave can be visited at most twice, and the remaining small
+                        // caves can be visited at most once.
+                        !path.contains(cave) || !has_double_visited_one_small_cave(path)
+                    } else {
+                        // Small caves can only be visited once.
+                        !path.contains(cave)
+                    }
+                }
+            },
+        )
+    }
+}
+
+fn has_double_visited_one_small_cave(path: &[Cave]) -> bool {
+    path.iter()
+        .enumerate()
+        .any(|(i, cave)| matches!(cave, Cave::Small(_)) && path[i + 1..].contains(cave))
+}
@@ -0,0 +1,152 @@
+use aoc::params::Params;
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+use std::fmt::Write;
+
+pub const DAY: Day = Day {
+    name: "day_13",
+    input: include_str!("../input"),
+    params: &[],
+    // PZFJHRFZ
+    answers: [
+        "610",
+        "\
+###..####.####...##.#..#.###..####.####.
+#..#....#.#.......#.#..#.#..#.#.......#.
+#..#...#..###.....#.####.#..#.###....#..
+###...#...#.......#.#..#.###..#.....#...
+#....#....#....#..#.#..#.#.#..#....#....
+#....####.#.....##..#..#.#..#.#....####.",
+    ],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let (mut grid, fold_instructions) = parse(input)?;
+    let (first, rest) = fold_instructions
+        .split_first()
+        .ok_or("expected at least one fold instruction")?;
+
+    grid.fold(first);
+
+    let part_1 = grid.count_dots();
+
+    for fold_instruction in rest {
+        grid.fold(fold_instruction);
+    }
+
+    // The code is spelled out in the dots, read it from the answer
+    let mut part_2 = String::new();
+    for row in grid.grid {
+        for d in row {
+            if d {
+                write!(part_2, "#").unwrap();
+            } else {
+                write!(part_2, ".").unwrap();
+            }
+        }
+        writeln!(part_2).unwrap();
+    }
+
+    Ok(Answers::new(part_1, part_2.trim()))
+}
+
+// The dot positions, a blank line, then the fold instructions.
+fn parse(input: &str) -> parse::Result<(Grid, Vec<FoldInstruction>)> {
+    Parser::parse(input, |p| {
+        let positions = p.section(|s| {
+            let lines = s.lines(|l| {
+                l.words(|w| {
+                    let x = w.integer()?;
+                    w.tag(",")?;
+                    Ok((x, w.integer()?))
+                })
+            })?;
+            Ok(lines.into_iter().flatten().collect::<Vec<_>>())
+        })?;
+
+        let fold_instructions = p.section(|s| {
+            s.lines(|l| {
+                l.tag("fold along ")?;
+                if l.eat("x=") {
+                    Ok(FoldInstruction::X(l.integer()?))
+                } else {
+                    l.tag("y=")?;
+                    Ok(FoldInstruction::Y(l.integer()?))
+                }
+            })
+        })?;
+
+        Ok((Grid::new(&positions), fold_instructions))
+    })
+}
+
+enum FoldInstruction {
+    X(usize),
+    Y(usize),
+}
+
+struct Grid {
+    grid: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    fn new(positions: &[(usize, usize)]) -> Self {
+        let max_x = *positions.iter().map(|(x, _)| x).max().unwrap() + 1;
+        let max_y = *positions.iter().map(|(_, y)| y).max().unwrap() + 1;
+        let mut grid = vec![vec![false; max_x]; max_y];
+        for &(x, y) in positions {
+            grid[y][x] = true;
+        }
+        Self { grid }
+    }
+
+    fn fold(&mut self, fold_instruction: &FoldInstruction) {
+        match fold_instruction {
+            FoldInstruction::X(n) => {
+                for row in self.grid.iter_mut() {
+                    let end = &row.split_off(*n)[1..];
+                    for (x, d) in end.iter().rev().enumerate() {
+                        if !row[x] {
+                            row[x] = *d;
+                        }
+                    }
+                }
+            }
+            FoldInstruction::Y(n) => {
+                let bottom = &self.grid.split_off(*n)[1..];
+                for (y, row) in bottom.iter().rev().enumerate() {
+                    for (x, d) in row.iter().enumerate() {
+                        if !self.grid[y][x] {
+                            self.grid[y][x] = *d;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn count_dots(&self) -> usize {
+        self.grid.iter().flatten().filter(|d| **d).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let example_1_positions = "6,10 0,14 9,10 0,3 10,4 4,11 6,0 6,12 4,1
+                                   0,13 10,12 3,4 3,0 8,4 1,10 2,14 8,10 9,0";
+        let example_1_fold_instructions = "fold along y=7\nfold along x=5";
+        let (mut grid, fold_instructions) = parse(&format!(
+            "{}\n\n{}",
+            example_1_positions, example_1_fold_instructions
+        ))
+        .unwrap();
+        grid.fold(&fold_instructions[0]);
+        assert_eq!(17, grid.count_dots());
+    }
+}
@@ -0,0 +1,94 @@
+use aoc::params::{Param, Params};
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+use std::collections::HashMap;
+
+type PairInsertionRules = HashMap<(char, char), char>;
+
+#[derive(Default, Debug, Clone)]
+struct PolymerTemplate {
+    template: HashMap<(char, char), u64>,
+    counts: HashMap<char, u64>,
+}
+
+impl PolymerTemplate {
+    fn new(input: &str) -> Self {
+        let chars: Vec<_> = input.chars().collect();
+        let mut pt = PolymerTemplate::default();
+        for i in 0..(chars.len() - 1) {
+            *pt.template.entry((chars[i], chars[i + 1])).or_insert(0) += 1;
+            // Keep counts of each char
+            *pt.counts.entry(chars[i]).or_insert(0) += 1;
+        }
+        // Because the above loop doesn't reach the last element,
+        // increase the count for the final char
+        *pt.counts.entry(*chars.last().unwrap()).or_insert(0) += 1;
+        pt
+    }
+
+    fn step(&mut self, pair_insertion_rules: &PairInsertionRules) {
+        for (key, count) in self.template.clone() {
+            if let Some(&value) = pair_insertion_rules.get(&key) {
+                *self.template.entry((key.0, value)).or_insert(0) += count;
+                *self.template.entry((value, key.1)).or_insert(0) += count;
+                *self.template.entry(key).or_insert(0) -= count;
+
+                // Increase the count any time we add a new char
+                *self.counts.entry(value).or_insert(0) += count;
+            }
+        }
+    }
+
+    fn result(&self) -> u64 {
+        let min = self.counts.values().min().unwrap();
+        let max = self.counts.values().max().unwrap();
+        max - min
+    }
+}
+
+pub const DAY: Day = Day {
+    name: "day_14",
+    input: include_str!("../input"),
+    params: &[
+        Param::new("part_1_steps", "10", "Pair insertion steps for part 1"),
+        Param::new("part_2_steps", "40", "Pair insertion steps for part 2"),
+    ],
+    answers: ["2712", "8336623059567"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let (polymer_template, pair_insertion_rules) = parse(input)?;
+
+    let result_after = |steps| {
+        let mut polymer_template = polymer_template.clone();
+        for _ in 0..steps {
+            polymer_template.step(&pair_insertion_rules);
+        }
+        polymer_template.result()
+    };
+
+    let part_1 = result_after(params.get("part_1_steps")?);
+    let part_2 = result_after(params.get("part_2_steps")?);
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+// The polymer template, a blank line, then the pair insertion rules, e.g. `CH -> B`.
+fn parse(input: &str) -> parse::Result<(PolymerTemplate, PairInsertionRules)> {
+    Parser::parse(input, |p| {
+        let polymer_template = p.section(|s| s.line(|l| l.word()))?;
+        let pair_insertion_rules = p.section(|s| {
+            s.lines(|l| {
+                let key = (l.char()?, l.char()?);
+                l.tag(" -> ")?;
+                Ok((key, l.char()?))
+            })
+        })?;
+        Ok((
+            PolymerTemplate::new(polymer_template),
+            pair_insertion_rules.into_iter().collect(),
+        ))
+    })
+}
@@ -0,0 +1,92 @@
+use aoc::graph;
+use aoc::grid::Grid;
+use aoc::params::Params;
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+
+pub const DAY: Day = Day {
+    name: "day_15",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["720", "3025"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let graph = Graph::new(input)?;
+
+    let part_1 = graph.lowest_risk_path();
+    let part_2 = graph.expand().lowest_risk_path();
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+struct Graph {
+    graph: Grid<u32>,
+}
+
+impl Graph {
+    fn new(input: &str) -> parse::Result<Self> {
+        let graph = Parser::parse(input, |p| p.grid(|c| c.to_digit(10)))?;
+        Ok(Self { graph })
+    }
+
+    // https://www.geeksforgeeks.org/dijkstras-shortest-path-algorithm-greedy-algo-7/
+    fn lowest_risk_path(&self) -> u32 {
+        let end = (self.graph.width() - 1, self.graph.height() - 1);
+        let (risk, _path) = graph::shortest_path(&self.graph, (0, 0), |&p| p == end).unwrap();
+        risk as u32
+    }
+
+    fn expand(&self) -> Graph {
+        let rollover = |n: u32| {
+            if n > 9 {
+                n - 9
+            } else {
+                n
+            }
+        };
+
+        let (width, height) = (self.graph.width(), self.graph.height());
+        let mut graph = Grid::filled(width * 5, height * 5, 0);
+
+        // Each tile to the right or downward is one higher than the tile to its left or above
+        for (x, y) in graph.positions() {
+            let increase = (x / width + y / height) as u32;
+            graph[(x, y)] = rollover(self.graph[(x % width, y % height)] + increase);
+        }
+
+        Graph { graph }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let input = "1163751742
+                     1381373672
+                     2136511328
+                     3694931569
+                     7463417111
+                     1319128137
+                     1359912421
+                     3125421639
+                     1293138521
+                     2311944581";
+        let graph = Graph::new(input).unwrap();
+
+        let part_1 = graph.lowest_risk_path();
+        assert_eq!(40, part_1);
+
+        let graph = graph.expand();
+
+        print!("{:?}", graph.graph);
+
+        let part_2 = graph.lowest_risk_path();
+        assert_eq!(315, part_2);
+    }
+}
@@ -0,0 +1,153 @@
+use aoc::params::{Param, Params};
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+pub const DAY: Day = Day {
+    name: "day_17",
+    input: include_str!("../input"),
+    params: &[Param::new(
+        "search_space",
+        "13000",
+        "How far to search for velocities and follow the probe in each direction",
+    )],
+    answers: ["12090", "5059"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let target_area = TargetArea::parse(input)?;
+    let search_space = params.get("search_space")?;
+
+    let mut positions = search(&target_area, search_space);
+
+    let part_1 = positions
+        .iter()
+        .max_by(|a, b| a.2.cmp(&b.2))
+        .ok_or("no velocity reaches the target area")?
+        .2;
+
+    positions.sort_unstable();
+    positions.dedup();
+    let part_2 = positions.len();
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+#[derive(Debug, Default)]
+struct Probe {
+    px: i32,
+    py: i32,
+    vx: i32,
+    vy: i32,
+    max_y: i32,
+}
+
+impl Probe {
+    fn new(vx: i32, vy: i32) -> Self {
+        Self {
+            vx,
+            vy,
+            ..Default::default()
+        }
+    }
+
+    fn iterate(target_area: &TargetArea, search_space: i32, x: i32, y: i32) -> Option<i32> {
+        let mut probe = Probe::new(x, y);
+        loop {
+            probe.step();
+            if target_area.contains(probe.px, probe.py) {
+                return Some(probe.max_y);
+            }
+            if probe.bounds_check(search_space) {
+                return None;
+            }
+        }
+    }
+
+    // - The probe's x position increases by its x velocity.
+    // - The probe's y position increases by its y velocity.
+    // - Due to drag, the probe's x velocity changes by 1 toward the value 0; that is, it decreases by
+    //   1 if it is greater than 0, increases by 1 if it is less than 0, or does not change if it is
+    //   already 0.
+    // - Due to gravity, the probe's y velocity decreases by 1.
+    fn step(&mut self) {
+        self.px += self.vx;
+        self.py += self.vy;
+
+        match self.vx.cmp(&0) {
+            Ordering::Less => self.vx += 1,
+            Ordering::Greater => self.vx -= 1,
+            Ordering::Equal => {}
+        }
+
+        self.vy -= 1;
+
+        self.max_y = self.max_y.max(self.py);
+    }
+
+    fn bounds_check(&self, search_space: i32) -> bool {
+        self.px.abs() > search_space
+            || self.py.abs() > search_space
+            || self.vx.abs() > search_space
+            || self.vy.abs() > search_space
+    }
+}
+
+struct TargetArea {
+    x: RangeInclusive<i32>,
+    y: RangeInclusive<i32>,
+}
+
+impl TargetArea {
+    fn new(x: RangeInclusive<i32>, y: RangeInclusive<i32>) -> Self {
+        Self { x, y }
+    }
+
+    // target area: x=20..30, y=-10..-5
+    fn parse(input: &str) -> parse::Result<Self> {
+        fn range(p: &mut Parser) -> parse::Result<RangeInclusive<i32>> {
+            let start = p.integer()?;
+            p.tag("..")?;
+            Ok(start..=p.integer()?)
+        }
+
+        Parser::parse(input, |p| {
+            p.tag("target area: x=")?;
+            let x = range(p)?;
+            p.tag(", y=")?;
+            Ok(Self::new(x, range(p)?))
+        })
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        self.x.contains(&x) && self.y.contains(&y)
+    }
+}
+
+fn search(target_area: &TargetArea, search_space: i32) -> Vec<(i32, i32, i32)> {
+    (-search_space..search_space)
+        .flat_map(|x| {
+            (-search_space..search_space).filter_map(move |y| {
+                Probe::iterate(target_area, search_space, x, y).map(|max_y| (x, y, max_y))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let target_area = TargetArea::parse("target area: x=20..30, y=-10..-5").unwrap();
+        assert_eq!(3, Probe::iterate(&target_area, 13_000, 7, 2).unwrap());
+        assert_eq!(6, Probe::iterate(&target_area, 13_000, 6, 3).unwrap());
+        assert_eq!(0, Probe::iterate(&target_area, 13_000, 9, 0).unwrap());
+        assert_eq!(45, Probe::iterate(&target_area, 13_000, 6, 9).unwrap());
+        assert!(Probe::iterate(&target_area, 13_000, 17, -4).is_none());
+    }
+}
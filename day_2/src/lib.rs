@@ -0,0 +1,52 @@
+use std::fmt;
+
+use aoc::params::Params;
+use aoc::{Answers, Day};
+
+use navigation::{Aim, Direct, Model, Trajectory};
+
+pub mod navigation;
+pub mod planner;
+pub mod script;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up(i32),
+    Down(i32),
+    Forward(i32),
+    Backward(i32),
+    ResetAim,
+}
+
+// Written the way `script` reads it
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Up(n) => write!(f, "up {}", n),
+            Direction::Down(n) => write!(f, "down {}", n),
+            Direction::Forward(n) => write!(f, "forward {}", n),
+            Direction::Backward(n) => write!(f, "backward {}", n),
+            Direction::ResetAim => write!(f, "reset aim"),
+        }
+    }
+}
+
+pub const DAY: Day = Day {
+    name: "day_2",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["2147104", "2044620088"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let commands = script::parse(input)?;
+
+    let [part_1, part_2] = [&Direct as &dyn Model, &Aim].map(|model| {
+        let end = Trajectory::follow(model, &commands).end();
+        end.position * end.depth
+    });
+
+    Ok(Answers::new(part_1, part_2))
+}
@@ -0,0 +1,284 @@
+// How the submarine moves for each command. The puzzle has two readings of the commands: part 1
+// moves straight up and down, part 2 turns `up` and `down` into changes of aim. Following a course
+// with a model records every state along the way.
+//
+//     cargo run --bin day_2 -- --trajectory                    # depth profiles for every model
+//     cargo run --bin day_2 -- --trajectory course.txt --model aim --csv > aim.csv
+
+use std::fmt::Write;
+
+use aoc::cli::Args;
+use aoc::grid::Grid;
+
+use crate::{script, Direction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct State {
+    pub position: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+pub trait Model {
+    fn name(&self) -> &'static str;
+
+    /// Where `direction` takes the submarine from `state`.
+    fn step(&self, state: State, direction: Direction) -> State;
+}
+
+/// Part 1: `up` and `down` change the depth directly. There's no aim.
+pub struct Direct;
+
+impl Model for Direct {
+    fn name(&self) -> &'static str {
+        "direct"
+    }
+
+    fn step(&self, s: State, direction: Direction) -> State {
+        match direction {
+            Direction::Up(n) => State {
+                depth: s.depth - i64::from(n),
+                ..s
+            },
+            Direction::Down(n) => State {
+                depth: s.depth + i64::from(n),
+                ..s
+            },
+            Direction::Forward(n) => State {
+                position: s.position + i64::from(n),
+                ..s
+            },
+            Direction::Backward(n) => State {
+                position: s.position - i64::from(n),
+                ..s
+            },
+            Direction::ResetAim => s,
+        }
+    }
+}
+
+/// Part 2: `up` and `down` change the aim, and moving changes the depth by the aim.
+pub struct Aim;
+
+impl Model for Aim {
+    fn name(&self) -> &'static str {
+        "aim"
+    }
+
+    fn step(&self, s: State, direction: Direction) -> State {
+        match direction {
+            Direction::Up(n) => State {
+                aim: s.aim - i64::from(n),
+                ..s
+            },
+            Direction::Down(n) => State {
+                aim: s.aim + i64::from(n),
+                ..s
+            },
+            Direction::Forward(n) => State {
+                position: s.position + i64::from(n),
+                depth: s.depth + s.aim * i64::from(n),
+                ..s
+            },
+            // Retraces the way forward would have gone
+            Direction::Backward(n) => State {
+                position: s.position - i64::from(n),
+                depth: s.depth - s.aim * i64::from(n),
+                ..s
+            },
+            Direction::ResetAim => State { aim: 0, ..s },
+        }
+    }
+}
+
+pub const MODELS: [&dyn Model; 2] = [&Direct, &Aim];
+
+/// The model called `name`.
+pub fn model(name: &str) -> Result<&'static dyn Model, String> {
+    MODELS
+        .iter()
+        .find(|m| m.name() == name)
+        .copied()
+        .ok_or_else(|| format!("unknown model `{}`, expected direct or aim", name))
+}
+
+/// Every state from the start, so `states[i]` is the state after `i` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    pub states: Vec<State>,
+}
+
+impl Trajectory {
+    pub fn follow(model: &dyn Model, commands: &[Direction]) -> Self {
+        let mut states = vec![State::default()];
+        for &direction in commands {
+            states.push(model.step(*states.last().unwrap(), direction));
+        }
+        Self { states }
+    }
+
+    pub fn end(&self) -> State {
+        *self.states.last().unwrap()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "step,position,depth,aim\n".to_string();
+        for (step, s) in self.states.iter().enumerate() {
+            writeln!(csv, "{},{},{},{}", step, s.position, s.depth, s.aim).unwrap();
+        }
+        csv
+    }
+
+    /// The depth against the position, scaled to fit in `width` by `height` characters. Depths
+    /// grow downwards from the surface, drawn as `~`.
+    pub fn profile(&self, width: usize, height: usize) -> String {
+        let range = |values: &mut dyn Iterator<Item = i64>| {
+            values.fold((0, 0), |(min, max), v| (v.min(min), v.max(max)))
+        };
+        let (min_x, max_x) = range(&mut self.states.iter().map(|s| s.position));
+        let (min_y, max_y) = range(&mut self.states.iter().map(|s| s.depth));
+        let scale = |v: i64, min: i64, max: i64, cells: usize| {
+            if max == min {
+                return 0;
+            }
+            ((v - min) as f64 / (max - min) as f64 * (cells - 1) as f64).round() as usize
+        };
+
+        let mut grid = Grid::filled(width, height, ' ');
+        let surface = scale(0, min_y, max_y, height);
+        for x in 0..width {
+            grid[(x, surface)] = '~';
+        }
+        for s in &self.states {
+            let x = scale(s.position, min_x, max_x, width);
+            let y = scale(s.depth, min_y, max_y, height);
+            grid[(x, y)] = '*';
+        }
+
+        // The depths at the top and bottom down the side, the positions at either end underneath
+        let labels = [min_y.to_string(), max_y.to_string()];
+        let label_width = labels.iter().map(|l| l.len()).max().unwrap();
+        let mut profile = String::new();
+        for (y, row) in grid.rows().enumerate() {
+            let label = match y {
+                0 => &labels[0],
+                y if y == height - 1 => &labels[1],
+                _ => "",
+            };
+            let row: String = row.iter().collect();
+            writeln!(
+                profile,
+                "{:>w$} |{}",
+                label,
+                row.trim_end(),
+                w = label_width
+            )
+            .unwrap();
+        }
+        let (left, right) = (min_x.to_string(), max_x.to_string());
+        writeln!(
+            profile,
+            "{:w$}  {}{:>r$}",
+            "",
+            left,
+            right,
+            w = label_width,
+            r = width.saturating_sub(left.len()).max(right.len() + 1)
+        )
+        .unwrap();
+        profile
+    }
+}
+
+/// `day_2 --trajectory`, following the bundled input unless given a file.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut models = Vec::new();
+    let mut csv = false;
+    let (mut width, mut height) = (72, 20);
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--model" | "-m" => models.push(model(&args.value(&arg)?)?),
+            "--csv" => csv = true,
+            "--width" => width = args.number(&arg, 2)?,
+            "--height" => height = args.number(&arg, 2)?,
+            _ => args.path(arg)?,
+        }
+    }
+    if models.is_empty() {
+        models = MODELS.to_vec();
+    }
+    if csv && models.len() > 1 {
+        return Err("--csv needs a single --model".into());
+    }
+
+    let input = args.input(crate::DAY.input)?;
+    let commands = script::parse(&input)?;
+
+    for model in models {
+        let trajectory = Trajectory::follow(model, &commands);
+        if csv {
+            print!("{}", trajectory.to_csv());
+            continue;
+        }
+        let end = trajectory.end();
+        println!(
+            "{}: position {}, depth {} after {} steps",
+            model.name(),
+            end.position,
+            end.depth,
+            commands.len()
+        );
+        println!("{}", trajectory.profile(width, height));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let commands = script::parse(
+            "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\nbackward 1\nreset aim\n",
+        )
+        .unwrap();
+
+        let direct = Trajectory::follow(&Direct, &commands);
+        assert_eq!(
+            State {
+                position: 14,
+                depth: 10,
+                aim: 0
+            },
+            direct.end()
+        );
+
+        let aim = Trajectory::follow(&Aim, &commands);
+        assert_eq!(9, aim.states.len());
+        assert_eq!(
+            State {
+                position: 14,
+                depth: 50,
+                aim: 0
+            },
+            aim.end()
+        );
+        assert!(aim
+            .to_csv()
+            .starts_with("step,position,depth,aim\n0,0,0,0\n1,5,0,0\n2,5,0,5\n"));
+
+        assert_eq!(
+            "\
+0 |*~*~~
+  |
+  |
+5 |  * *
+   0  13
+",
+            Trajectory::follow(&Direct, &commands[..3]).profile(5, 4)
+        );
+    }
+}
@@ -0,0 +1,407 @@
+// Working backwards from where the submarine should end up to the commands that take it there.
+// Plans are as short as possible, and come out in the same format `script` reads, so they can be
+// followed to check them.
+//
+//     cargo run --bin day_2 -- --plan 15,60 --model aim --max-step 4 > course.txt
+//     cargo run --bin day_2 -- --trajectory course.txt --model aim
+
+use std::cell::Cell;
+use std::convert::TryFrom;
+
+use aoc::cli::{self, Args};
+use aoc::graph;
+
+use crate::navigation::{self, Model, State, Trajectory, MODELS};
+use crate::{script, Direction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    /// The submarine never goes deeper than this.
+    pub max_depth: Option<i64>,
+    /// No command moves or turns by more than this.
+    pub max_step: Option<i32>,
+}
+
+/// The most states `search` moves on from before giving up.
+pub const MAX_STATES: usize = 200_000;
+
+/// The largest maximum step `search` tries every step up to.
+pub const MAX_SEARCH_STEP: i32 = 1_000;
+
+/// The furthest a target's position or depth can be from the start, so the aim planner's search
+/// for divisors of the depth stays quick and nothing it works out can overflow.
+pub const MAX_TARGET: i64 = 1_000_000_000_000;
+
+// An error if the target is further away than `MAX_TARGET`
+fn check_target(position: i64, depth: i64) -> Result<(), String> {
+    if position.unsigned_abs() > MAX_TARGET as u64 || depth.unsigned_abs() > MAX_TARGET as u64 {
+        return Err(format!(
+            "position {}, depth {} is out of reach, targets can be at most {} away",
+            position, depth, MAX_TARGET
+        ));
+    }
+    Ok(())
+}
+
+/// The shortest course to `position` and `depth` under `model`, checked by following it. Only
+/// the direct and aim models have planners.
+pub fn plan(
+    model: &dyn Model,
+    position: i64,
+    depth: i64,
+    limits: Limits,
+) -> Result<Vec<Direction>, String> {
+    if limits.max_step.is_some_and(|step| step < 1) {
+        return Err("the maximum step must be at least 1".to_string());
+    }
+    if let Some(max_depth) = limits.max_depth {
+        if max_depth < 0 || depth > max_depth {
+            return Err(format!("depth {} is out of reach", depth));
+        }
+    }
+
+    let commands = match model.name() {
+        "direct" => direct(position, depth, limits)?,
+        "aim" => aim(position, depth, limits)?,
+        name => return Err(format!("there's no planner for the {} model", name)),
+    };
+    let trajectory = Trajectory::follow(model, &commands);
+    let end = trajectory.end();
+    if (end.position, end.depth) != (position, depth) {
+        return Err(format!(
+            "the plan ends at position {}, depth {} instead",
+            end.position, end.depth
+        ));
+    }
+    if let Some(max_depth) = limits.max_depth {
+        if trajectory.states.iter().any(|s| s.depth > max_depth) {
+            return Err(format!("the plan goes deeper than {}", max_depth));
+        }
+    }
+    Ok(commands)
+}
+
+// As many commands as it takes to go `distance` without going more than `max_step` at once, using
+// `forward` for positive distances and `backward` for negative ones
+fn steps(
+    distance: i64,
+    max_step: Option<i32>,
+    forward: fn(i32) -> Direction,
+    backward: fn(i32) -> Direction,
+) -> Result<Vec<Direction>, String> {
+    let direction = if distance < 0 { backward } else { forward };
+    let max_step = max_step.map_or(i64::MAX, i64::from);
+    let mut left = distance.abs();
+    // A plan has to read back in as a script
+    if (left as u64).div_ceil(max_step as u64) > script::MAX_COMMANDS as u64 {
+        return Err(format!(
+            "going {} takes more than {} commands",
+            distance,
+            script::MAX_COMMANDS
+        ));
+    }
+    let mut commands = Vec::new();
+    while left > 0 {
+        let step = left.min(max_step);
+        let step =
+            i32::try_from(step).map_err(|_| format!("{} is too far for one command", step))?;
+        commands.push(direction(step));
+        left -= i64::from(step);
+    }
+    Ok(commands)
+}
+
+/// Under the direct model each command changes just one of the position and depth, so the
+/// shortest plan covers each with the fewest, largest steps. Going down in one direction means it's
+/// never deeper than the target.
+pub fn direct(position: i64, depth: i64, limits: Limits) -> Result<Vec<Direction>, String> {
+    check_target(position, depth)?;
+    let mut commands = steps(
+        position,
+        limits.max_step,
+        Direction::Forward,
+        Direction::Backward,
+    )?;
+    commands.extend(steps(
+        depth,
+        limits.max_step,
+        Direction::Down,
+        Direction::Up,
+    )?);
+    Ok(commands)
+}
+
+/// Under the aim model with no limit on the steps every target takes at most three commands:
+///
+/// - none or a single move if the target is at the surface,
+/// - an aim of `depth / position` then a move if that divides exactly,
+/// - otherwise a move at the surface, an aim of 1 and a move of `depth`.
+///
+/// Two commands can't reach anything else: the depth only changes when moving with an aim, and
+/// that takes both commands. The depth only ever heads towards the target's depth.
+///
+/// With a limit on the steps the best plan of the same shape, moving at the surface, turning to
+/// some aim then moving on, is the answer unless a search finds something shorter.
+pub fn aim(position: i64, depth: i64, limits: Limits) -> Result<Vec<Direction>, String> {
+    check_target(position, depth)?;
+    let turn = |aim: i64| steps(aim, limits.max_step, Direction::Down, Direction::Up);
+    let go = |distance: i64| {
+        steps(
+            distance,
+            limits.max_step,
+            Direction::Forward,
+            Direction::Backward,
+        )
+    };
+    let plan = |surface: i64, aim: i64| -> Result<Vec<Direction>, String> {
+        Ok([go(surface)?, turn(aim)?, go(position - surface)?].concat())
+    };
+
+    if depth == 0 {
+        return go(position);
+    }
+    let max_step = match limits.max_step {
+        Some(max_step) => i64::from(max_step),
+        None if position != 0 && depth % position == 0 => return plan(0, depth / position),
+        None => return plan(position - depth, 1),
+    };
+
+    // Any aim that divides the depth works, moving the rest of the way at the surface
+    let length = |n: i64| (n.abs() + max_step - 1) / max_step;
+    let mut best = (i64::MAX, 0, 0);
+    let mut divisor = 1;
+    while divisor <= depth.abs() / divisor {
+        if depth % divisor == 0 {
+            for &aim in &[divisor, -divisor, depth / divisor, -depth / divisor] {
+                let surface = position - depth / aim;
+                let commands = length(surface) + length(aim) + length(depth / aim);
+                best = best.min((commands, surface, aim));
+            }
+        }
+        divisor += 1;
+    }
+    let (_, surface, aim) = best;
+    let best = plan(surface, aim)?;
+
+    // The fewest commands that could finish from `s`. With `k` of `r` commands turning, the aim
+    // never gets past `|aim| + k * max_step`, so the other moves change the depth by at most
+    // `(r - k) * (|aim| + k * max_step) * max_step`. That's largest with `k` around
+    // `(r * max_step - |aim|) / (2 * max_step)`.
+    let estimate = |s: &State| {
+        let moves = length(position - s.position);
+        let left = i128::from((depth - s.depth).abs());
+        if left == 0 {
+            return moves as usize;
+        }
+        let reach = |r: i64, k: i64| {
+            i128::from(r - k) * i128::from(s.aim.abs() + k * max_step) * i128::from(max_step)
+        };
+        (moves.max(1)..)
+            .find(|&r| {
+                let best = (r * max_step - s.aim.abs()) / (2 * max_step);
+                [best, best + 1]
+                    .iter()
+                    .map(|&k| k.clamp(0, r - moves.max(1)))
+                    .any(|k| reach(r, k) >= left)
+            })
+            .unwrap() as usize
+    };
+    if estimate(&State::default()) == best.len() {
+        return Ok(best);
+    }
+    let shorter = search(
+        &crate::navigation::Aim,
+        position,
+        depth,
+        limits,
+        estimate,
+        best.len(),
+    )?;
+    Ok(shorter.unwrap_or(best))
+}
+
+// An A* search for a plan under any model shorter than `bound`, one step of up to
+// `limits.max_step` at a time, guided by an `estimate` of the commands left that's never too high.
+// It gives up after `MAX_STATES` states.
+fn search(
+    model: &dyn Model,
+    position: i64,
+    depth: i64,
+    limits: Limits,
+    estimate: impl Fn(&State) -> usize,
+    bound: usize,
+) -> Result<Option<Vec<Direction>>, String> {
+    let max_step = limits
+        .max_step
+        .ok_or("searching for a plan needs a maximum step")?;
+    if max_step > MAX_SEARCH_STEP {
+        return Err(format!(
+            "can't search with steps over {}, try a smaller maximum step",
+            MAX_SEARCH_STEP
+        ));
+    }
+    let mut commands = vec![Direction::ResetAim];
+    for n in 1..=max_step {
+        commands.extend_from_slice(&[
+            Direction::Forward(n),
+            Direction::Backward(n),
+            Direction::Down(n),
+            Direction::Up(n),
+        ]);
+    }
+
+    // Nodes carry how many commands it took to get there, so anything that can't finish in fewer
+    // than `bound` can be left out. That also keeps the search finite.
+    let visited = Cell::new(0);
+    let graph = graph::from_fn(|&(state, length): &(State, usize)| {
+        visited.set(visited.get() + 1);
+        if visited.get() > MAX_STATES {
+            return Vec::new();
+        }
+        commands
+            .iter()
+            .map(|&c| model.step(state, c))
+            .filter(|s| {
+                *s != state
+                    && limits.max_depth.is_none_or(|max| s.depth <= max)
+                    && length + 1 + estimate(s) < bound
+            })
+            .map(|s| ((s, length + 1), 1))
+            .collect()
+    });
+
+    let goal = |&(s, _): &(State, usize)| s.position == position && s.depth == depth;
+    let estimate = |&(s, _): &(State, usize)| estimate(&s) as u64;
+    let found = graph::a_star(&graph, (State::default(), 0), goal, estimate);
+    // Past the cap some states weren't moved on from, so whatever was found might not be shortest
+    if visited.get() > MAX_STATES {
+        return Err(format!(
+            "gave up after {} states, try a larger maximum step",
+            MAX_STATES
+        ));
+    }
+    let states = match found {
+        Some((_, path)) => path,
+        None => return Ok(None),
+    };
+
+    // Work out which command took each state to the next
+    Ok(Some(
+        states
+            .windows(2)
+            .map(|pair| {
+                *commands
+                    .iter()
+                    .find(|&&c| model.step(pair[0].0, c) == pair[1].0)
+                    .unwrap()
+            })
+            .collect(),
+    ))
+}
+
+/// `day_2 --plan POSITION,DEPTH`, printing the plan as a script.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut target = None;
+    let mut model = MODELS[1];
+    let mut limits = Limits::default();
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--model" | "-m" => model = navigation::model(&args.value(&arg)?)?,
+            "--max-depth" => limits.max_depth = Some(args.parse(&arg, "a number")?),
+            "--max-step" => limits.max_step = Some(args.number(&arg, 1)?),
+            _ if target.is_none() => {
+                let parsed = arg
+                    .split_once(',')
+                    .and_then(|(p, d)| Some((p.trim().parse().ok()?, d.trim().parse().ok()?)));
+                target = Some(parsed.ok_or_else(|| {
+                    format!("expected the target as POSITION,DEPTH, not `{}`", arg)
+                })?);
+            }
+            _ => return Err(cli::unexpected(&arg).into()),
+        }
+    }
+    let (position, depth) = target.ok_or("--plan needs a target POSITION,DEPTH")?;
+
+    let plan = plan(model, position, depth, limits)?;
+    let text: String = plan.iter().map(|c| format!("{}\n", c)).collect();
+    // What's printed has to read back as the same plan
+    if script::parse(&text)? != plan {
+        return Err("the plan doesn't read back as the same commands".into());
+    }
+    print!("{}", text);
+    eprintln!(
+        "{}: {} commands to position {}, depth {}",
+        model.name(),
+        plan.len(),
+        position,
+        depth
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::navigation::{Aim, Direct};
+    use Direction::*;
+
+    #[test]
+    fn it_works() {
+        let limits = Limits::default();
+        assert_eq!(
+            Ok(vec![Forward(15), Down(10)]),
+            plan(&Direct, 15, 10, limits)
+        );
+        assert_eq!(Ok(vec![Down(4), Forward(15)]), plan(&Aim, 15, 60, limits));
+        assert_eq!(
+            Ok(vec![Forward(8), Down(1), Forward(7)]),
+            plan(&Aim, 15, 7, limits)
+        );
+        assert_eq!(
+            Ok(vec![Backward(25), Down(1), Forward(5)]),
+            plan(&Aim, -20, 5, limits)
+        );
+
+        let limits = Limits {
+            max_depth: Some(100),
+            max_step: Some(6),
+        };
+        assert_eq!(
+            Ok(vec![Forward(6), Forward(6), Forward(3), Down(6), Down(4)]),
+            plan(&Direct, 15, 10, limits)
+        );
+        let course = plan(&Aim, 15, 60, limits).unwrap();
+        assert_eq!(4, course.len());
+        // Going back and forth beats anything of the simpler shape
+        assert_eq!(Ok(4), plan(&Aim, 0, -11, limits).map(|c| c.len()));
+
+        // The plan can be read back in
+        let text: String = course.iter().map(|c| format!("{}\n", c)).collect();
+        assert_eq!(course, script::parse(&text).unwrap());
+
+        assert_eq!(
+            Err("depth 101 is out of reach".to_string()),
+            plan(&Aim, 15, 101, limits)
+        );
+
+        // Far targets are errors rather than overflows
+        for model in MODELS {
+            assert!(plan(model, i64::MIN, i64::MAX, Limits::default()).is_err());
+            assert!(plan(model, 1, -MAX_TARGET - 1, Limits::default()).is_err());
+        }
+        let limits = Limits {
+            max_depth: None,
+            max_step: Some(1000),
+        };
+        assert_eq!(
+            Err("going 999999999999 takes more than 10000000 commands".to_string()),
+            plan(&Aim, MAX_TARGET, MAX_TARGET - 1, limits)
+        );
+        assert_eq!(
+            Ok(2),
+            plan(&Aim, 1000, MAX_TARGET, Limits::default()).map(|c| c.len())
+        );
+    }
+}
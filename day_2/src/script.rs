@@ -0,0 +1,248 @@
+// The command language the course is written in. On top of the puzzle's `forward 5` lines it has
+// comments, `repeat N { … }` blocks and macros, which are expanded into plain commands as they're
+// parsed:
+//
+//     # Dive in stages
+//     macro dive {
+//         down 5
+//         forward 2   # level off
+//     }
+//     repeat 3 {
+//         dive
+//     }
+//     backward 4
+//     reset aim
+
+use std::collections::HashMap;
+
+use aoc::parse::{self, Parser};
+
+use crate::Direction;
+
+/// The most commands a script can expand to, so a typo in a `repeat` can't eat all the memory.
+pub const MAX_COMMANDS: usize = 10_000_000;
+
+/// The commands macros can't be named after, as they'd never be called.
+const BUILT_IN: [&str; 7] = [
+    "forward", "backward", "down", "up", "reset", "repeat", "macro",
+];
+
+enum Line<'a> {
+    Blank,
+    Command(Direction),
+    Repeat(usize),
+    Macro(&'a str),
+    /// The commands in a macro.
+    Call(Vec<Direction>),
+    Close,
+}
+
+/// Every command `input` expands to.
+pub fn parse(input: &str) -> parse::Result<Vec<Direction>> {
+    let mut p = Parser::new(input);
+    let commands = block(&mut p, &mut HashMap::new(), None)?;
+    p.complete(|_| Ok(commands))
+}
+
+// The commands up to the `}` closing the block opened at `open`, or up to the end of the input at
+// the top level
+fn block<'a>(
+    p: &mut Parser<'a>,
+    macros: &mut HashMap<&'a str, Vec<Direction>>,
+    open: Option<Parser<'a>>,
+) -> parse::Result<Vec<Direction>> {
+    let mut commands = Vec::new();
+    loop {
+        p.skip_whitespace();
+        if p.is_empty() {
+            return match open {
+                Some(open) => Err(open.error("missing `}` for this block")),
+                None => Ok(commands),
+            };
+        }
+
+        let start = *p;
+        let (body, times) = match p.line(|l| line(l, macros))? {
+            Line::Blank => continue,
+            Line::Command(direction) => (vec![direction], 1),
+            Line::Close if open.is_some() => return Ok(commands),
+            Line::Close => return Err(start.error("unexpected `}`")),
+            Line::Repeat(times) => (block(p, macros, Some(start))?, times),
+            Line::Macro(name) => {
+                if macros.contains_key(name) {
+                    return Err(start.error(format!("macro `{}` is already defined", name)));
+                }
+                let body = block(p, macros, Some(start))?;
+                macros.insert(name, body);
+                continue;
+            }
+            Line::Call(body) => (body, 1),
+        };
+
+        if commands.len() + body.len().saturating_mul(times) > MAX_COMMANDS {
+            return Err(start.error(format!(
+                "the script expands to more than {} commands",
+                MAX_COMMANDS
+            )));
+        }
+        // An empty block repeated any number of times is still nothing
+        if !body.is_empty() {
+            for _ in 0..times {
+                commands.extend_from_slice(&body);
+            }
+        }
+    }
+}
+
+fn line<'a>(
+    l: &mut Parser<'a>,
+    macros: &HashMap<&'a str, Vec<Direction>>,
+) -> parse::Result<Line<'a>> {
+    let line = if l.is_empty() || l.peek() == Some('#') {
+        Line::Blank
+    } else if l.eat("}") {
+        Line::Close
+    } else {
+        let start = *l;
+        match name(l)? {
+            "forward" => Line::Command(Direction::Forward(amount(l)?)),
+            "backward" => Line::Command(Direction::Backward(amount(l)?)),
+            "down" => Line::Command(Direction::Down(amount(l)?)),
+            "up" => Line::Command(Direction::Up(amount(l)?)),
+            "reset" => {
+                l.tag(" ")?;
+                l.skip_spaces();
+                l.tag("aim")?;
+                Line::Command(Direction::ResetAim)
+            }
+            "repeat" => {
+                l.tag(" ")?;
+                l.skip_spaces();
+                let times = l.integer()?;
+                open_brace(l)?;
+                Line::Repeat(times)
+            }
+            "macro" => {
+                l.tag(" ")?;
+                l.skip_spaces();
+                let start = *l;
+                let name = name(l)?;
+                if BUILT_IN.contains(&name) {
+                    return Err(start.error(format!("`{}` is already a command", name)));
+                }
+                open_brace(l)?;
+                Line::Macro(name)
+            }
+            name => match macros.get(name) {
+                Some(body) => Line::Call(body.clone()),
+                None => return Err(start.error(format!("unknown command `{}`", name))),
+            },
+        }
+    };
+
+    // Anything after a `#` is a comment
+    l.skip_spaces();
+    if l.eat("#") {
+        l.take_while(|_| true);
+    }
+    Ok(line)
+}
+
+// Letters, digits and underscores, starting with a letter
+fn name<'a>(l: &mut Parser<'a>) -> parse::Result<&'a str> {
+    let start = *l;
+    match l.peek() {
+        Some(c) if c.is_ascii_alphabetic() => {
+            Ok(l.take_while(|c| c.is_ascii_alphanumeric() || c == '_'))
+        }
+        _ => Err(start.error(format!(
+            "expected a command, found `{}`",
+            start.remaining().lines().next().unwrap_or_default()
+        ))),
+    }
+}
+
+fn amount(l: &mut Parser) -> parse::Result<i32> {
+    l.tag(" ")?;
+    l.skip_spaces();
+    l.integer()
+}
+
+fn open_brace(l: &mut Parser) -> parse::Result<()> {
+    l.skip_spaces();
+    l.tag("{")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Direction::*;
+
+    #[test]
+    fn it_works() {
+        let script = "\
+# Dive in stages
+macro dive {
+    down 5
+    forward 2   # level off
+}
+
+repeat 2 {
+    dive
+    repeat 2 { # nested
+        up 1
+    }
+}
+backward 4
+reset aim
+";
+        assert_eq!(
+            vec![
+                Down(5),
+                Forward(2),
+                Up(1),
+                Up(1),
+                Down(5),
+                Forward(2),
+                Up(1),
+                Up(1),
+                Backward(4),
+                ResetAim
+            ],
+            parse(script).unwrap()
+        );
+
+        let empty = format!("repeat {} {{\n}}\nup 1", usize::MAX);
+        assert_eq!(vec![Up(1)], parse(&empty).unwrap());
+    }
+
+    #[test]
+    fn errors() {
+        let error = |script| parse(script).unwrap_err().to_string();
+        assert_eq!(
+            "line 2, column 1: unknown command `sideways`",
+            error("up 1\nsideways 3\n")
+        );
+        assert_eq!(
+            "line 1, column 9: expected an integer, found `far`",
+            error("forward far")
+        );
+        assert_eq!(
+            "line 2, column 1: missing `}` for this block",
+            error("up 1\nrepeat 3 {\n  up 1\n")
+        );
+        assert_eq!("line 1, column 1: unexpected `}`", error("}"));
+        assert_eq!(
+            "line 3, column 3: unknown command `dive`",
+            error("macro sink {\n  down 1\n  dive\n}\n")
+        );
+        assert_eq!(
+            "line 1, column 7: `up` is already a command",
+            error("macro up {\n  down 1\n}\n")
+        );
+        assert_eq!(
+            "line 1, column 1: the script expands to more than 10000000 commands",
+            error("repeat 100000000 {\n  up 1\n}")
+        );
+    }
+}
@@ -0,0 +1,244 @@
+// How the diagnostic numbers are stored: in a `u64` or `u128` when they fit, and as a vector of
+// words when they don't. Bit 0 is the least significant, i.e. the last digit of the line.
+
+use std::cmp::Ordering;
+
+use aoc::bigint::BigUint;
+
+pub trait Bits: Clone + Ord {
+    /// The number written as `digits`, a non-empty string of `0`s and `1`s that fits.
+    fn from_digits(digits: &str) -> Self {
+        Self::parse_digits(digits.as_bytes()).unwrap()
+    }
+
+    /// `from_digits`, or `None` if there's anything other than `0`s and `1`s.
+    fn parse_digits(digits: &[u8]) -> Option<Self>;
+
+    fn bit(&self, i: usize) -> bool;
+
+    /// The number's value, which may not fit in any integer type.
+    fn value(&self, width: usize) -> BigUint {
+        BigUint::from_bits((0..width).rev().map(|i| self.bit(i)))
+    }
+
+    /// The number with its lowest `width` bits in the opposite order.
+    fn reverse(&self, width: usize) -> Self;
+
+    /// Sort numbers that are `width` bits wide.
+    fn sort(numbers: &mut [Self], _width: usize) {
+        numbers.sort_unstable();
+    }
+
+    /// How many of `numbers` have each bit set, indexed by bit.
+    fn column_ones(numbers: &[Self], width: usize) -> Vec<usize> {
+        (0..width)
+            .map(|i| numbers.iter().filter(|n| n.bit(i)).count())
+            .collect()
+    }
+}
+
+macro_rules! bits {
+    ($($t:ty)*) => {
+        $(
+            impl Bits for $t {
+                // Checking every digit as it goes rather than bailing out early, which is quicker
+                // as it's almost always fine
+                fn parse_digits(digits: &[u8]) -> Option<Self> {
+                    let (n, valid) = digits.iter().fold((0, true), |(n, valid), &digit| {
+                        let bit = digit.wrapping_sub(b'0');
+                        (n << 1 | <$t>::from(bit & 1), valid & (bit <= 1))
+                    });
+                    Some(n).filter(|_| valid)
+                }
+
+                fn bit(&self, i: usize) -> bool {
+                    self >> i & 1 == 1
+                }
+
+                fn value(&self, _width: usize) -> BigUint {
+                    BigUint::from(*self as u128)
+                }
+
+                fn reverse(&self, width: usize) -> Self {
+                    if width == 0 {
+                        return 0;
+                    }
+                    self.reverse_bits() >> (<$t>::BITS as usize - width)
+                }
+
+                // A counting sort for narrow numbers, otherwise a radix sort `RADIX_BITS` at a time
+                // from the least significant
+                fn sort(numbers: &mut [Self], width: usize) {
+                    if numbers.len() < RADIX_SORT_LEN {
+                        numbers.sort_unstable();
+                    } else if width <= COUNTING_SORT_BITS {
+                        let mut counts = vec![0; 1 << width];
+                        for &n in numbers.iter() {
+                            counts[n as usize] += 1;
+                        }
+                        let mut start = 0;
+                        for (n, &count) in counts.iter().enumerate() {
+                            numbers[start..start + count].fill(n as $t);
+                            start += count;
+                        }
+                    } else {
+                        let mut buffer = vec![0; numbers.len()];
+                        let mut in_buffer = false;
+                        for shift in (0..width).step_by(RADIX_BITS) {
+                            let (from, to) = if in_buffer {
+                                (&*buffer, &mut *numbers)
+                            } else {
+                                (&*numbers, &mut *buffer)
+                            };
+                            let digit = |n: $t| (n >> shift) as usize & ((1 << RADIX_BITS) - 1);
+                            let mut starts = vec![0; 1 << RADIX_BITS];
+                            for &n in from {
+                                starts[digit(n)] += 1;
+                            }
+                            let mut start = 0;
+                            for count in &mut starts {
+                                start += *count;
+                                *count = start - *count;
+                            }
+                            for &n in from {
+                                let start = &mut starts[digit(n)];
+                                to[*start] = n;
+                                *start += 1;
+                            }
+                            in_buffer = !in_buffer;
+                        }
+                        if in_buffer {
+                            numbers.copy_from_slice(&buffer);
+                        }
+                    }
+                }
+
+                // Counting each byte value in each byte position takes one pass, and how many
+                // ones each column has follows from those counts
+                fn column_ones(numbers: &[Self], width: usize) -> Vec<usize> {
+                    let bytes = width.div_ceil(8);
+                    let mut counts = vec![[0usize; 256]; bytes];
+                    for &n in numbers {
+                        for (i, counts) in counts.iter_mut().enumerate() {
+                            counts[(n >> (8 * i)) as usize & 0xff] += 1;
+                        }
+                    }
+                    (0..width)
+                        .map(|bit| {
+                            let counts = &counts[bit / 8];
+                            (0..256)
+                                .filter(|byte| byte >> (bit % 8) & 1 == 1)
+                                .map(|byte| counts[byte])
+                                .sum()
+                        })
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+bits!(u64 u128);
+
+/// Below this many numbers it's quicker to sort them by comparison.
+const RADIX_SORT_LEN: usize = 1 << 16;
+
+/// How many bits each pass of the radix sort sorts by. Few enough that the counts stay in cache.
+const RADIX_BITS: usize = 11;
+
+/// Numbers up to this wide are sorted by counting how many there are of each.
+const COUNTING_SORT_BITS: usize = 16;
+
+/// A number too wide for a `u128`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitVec {
+    words: Vec<u64>,
+}
+
+impl Bits for BitVec {
+    fn parse_digits(digits: &[u8]) -> Option<Self> {
+        let mut words = vec![0; digits.len().div_ceil(64)];
+        for (i, &digit) in digits.iter().rev().enumerate() {
+            match digit {
+                b'0' => (),
+                b'1' => words[i / 64] |= 1 << (i % 64),
+                _ => return None,
+            }
+        }
+        Some(Self { words })
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    fn reverse(&self, width: usize) -> Self {
+        let mut words = vec![0; self.words.len()];
+        for i in (0..width).filter(|&i| self.bit(i)) {
+            let j = width - 1 - i;
+            words[j / 64] |= 1 << (j % 64);
+        }
+        Self { words }
+    }
+}
+
+// Numbers compare by value, so from the most significant word down
+impl Ord for BitVec {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words.iter().rev().cmp(other.words.iter().rev())
+    }
+}
+
+impl PartialOrd for BitVec {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let digits = format!("1{}01", "0".repeat(126));
+        let n = BitVec::from_digits(&digits);
+        assert!(n.bit(0) && !n.bit(1) && n.bit(128) && !n.bit(127));
+        assert_eq!(
+            BigUint::from_bits(digits.chars().map(|c| c == '1')),
+            n.value(129)
+        );
+
+        assert_eq!(
+            u128::from_digits(&digits[1..]).value(128),
+            BitVec::from_digits(&digits[1..]).value(128)
+        );
+        let zeros = "0".repeat(127);
+        assert_eq!(
+            BitVec::from_digits(&format!("{}11", zeros)),
+            BitVec::from_digits(&format!("11{}", zeros)).reverse(129)
+        );
+        assert_eq!(0b0011, 0b1100u64.reverse(4));
+        assert!(BitVec::from_digits(&digits) > BitVec::from_digits(&digits[1..]));
+
+        assert_eq!(Some(0b101), u64::parse_digits(b"101"));
+        assert_eq!(None, u64::parse_digits(b"1a1"));
+        assert_eq!(None, BitVec::parse_digits(b"1a1"));
+
+        // Big enough for a radix sort
+        let mut numbers: Vec<u64> = (0..100_000u64).map(|i| i * 7919 % 100_003).collect();
+        let mut sorted = numbers.clone();
+        sorted.sort_unstable();
+        let mut counted = numbers.iter().map(|n| n % 1000).collect::<Vec<_>>();
+        u64::sort(&mut numbers, 17);
+        assert_eq!(sorted, numbers);
+        sorted = counted.clone();
+        sorted.sort_unstable();
+        u64::sort(&mut counted, 10);
+        assert_eq!(sorted, counted);
+        assert_eq!(
+            vec![50_000, 50_000, 50_000, 50_000, 50_000, 49_984, 49_984, 49_952, 49_920, 49_824],
+            u64::column_ones(&(0..100_000).collect::<Vec<_>>(), 10)
+        );
+    }
+}
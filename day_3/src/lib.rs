@@ -0,0 +1,128 @@
+use aoc::bigint::BigUint;
+use aoc::params::Params;
+use aoc::parse::Parser;
+use aoc::{Answers, Day};
+
+pub mod bits;
+pub mod rating;
+
+use bits::{BitVec, Bits};
+use rating::{Index, Order, CO2_SCRUBBER, OXYGEN_GENERATOR};
+
+pub const DAY: Day = Day {
+    name: "day_3",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["2640986", "6822109"],
+    solve,
+    alternatives: &[],
+};
+
+/// How many bits wide the numbers in the report are, going by the first line.
+pub fn width(input: &str) -> aoc::Result<usize> {
+    let mut p = Parser::new(input);
+    p.skip_whitespace();
+    if p.is_empty() {
+        return Err("the report is empty".into());
+    }
+    match p.take_while(|c| c == '0' || c == '1').len() {
+        0 => Err(p.error("expected binary digits").into()),
+        width => Ok(width),
+    }
+}
+
+/// The numbers in the report, every one `width` bits wide.
+pub fn parse<B: Bits>(input: &str, width: usize) -> aoc::Result<Vec<B>> {
+    // Reports can be huge, so lines that are just digits skip the parser. Anything else goes
+    // through it, to allow for stray spaces or report what's wrong.
+    let mut numbers = Vec::with_capacity(input.len() / (width + 1));
+    for line in input.split('\n') {
+        match B::parse_digits(line.as_bytes()) {
+            Some(n) if line.len() == width => numbers.push(n),
+            _ if line.trim().is_empty() => (),
+            _ => return parse_slowly(input, width),
+        }
+    }
+    Ok(numbers)
+}
+
+fn parse_slowly<B: Bits>(input: &str, width: usize) -> aoc::Result<Vec<B>> {
+    let numbers = Parser::parse(input, |p| {
+        p.lines(|l| {
+            let digits = l.take_while(|c| c == '0' || c == '1');
+            if digits.len() != width {
+                return Err(l.error(format!(
+                    "expected {} bits like the first line, found {}",
+                    width,
+                    digits.len()
+                )));
+            }
+            Ok(B::from_digits(digits))
+        })
+    })?;
+    Ok(numbers)
+}
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let width = width(input)?;
+    if width <= 64 {
+        solve_with::<u64>(input, width)
+    } else if width <= 128 {
+        solve_with::<u128>(input, width)
+    } else {
+        solve_with::<BitVec>(input, width)
+    }
+}
+
+fn solve_with<B: Bits>(input: &str, bit_count: usize) -> aoc::Result<Answers> {
+    let input: Vec<B> = parse(input, bit_count)?;
+
+    // Part 1
+    let ones = B::column_ones(&input, bit_count);
+    let gamma_rate = BigUint::from_bits((0..bit_count).rev().map(|i| ones[i] * 2 >= input.len()));
+    let epsilon_rate = BigUint::from_bits((0..bit_count).rev().map(|i| ones[i] * 2 < input.len()));
+    let part_1 = &gamma_rate * &epsilon_rate;
+
+    // Part 2
+    let index = Index::new(input, bit_count, Order::MostSignificantFirst);
+    let oxygen_generator_rating = OXYGEN_GENERATOR.select_in(&index)?.value.value(bit_count);
+    let c02_scrubber_rating = CO2_SCRUBBER.select_in(&index)?.value.value(bit_count);
+
+    let part_2 = &oxygen_generator_rating * &c02_scrubber_rating;
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str =
+        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010";
+
+    #[test]
+    fn it_works() {
+        let answers = solve(EXAMPLE, &Params::new(&[])).unwrap();
+        assert_eq!(("198", "230"), (&*answers.part_1, &*answers.part_2));
+
+        // Wider reports come out the same either way they're stored
+        let wide: Vec<_> = EXAMPLE.lines().map(|l| l.repeat(25)).collect();
+        let wide = wide.join("\n");
+        assert_eq!(
+            solve_with::<u128>(&wide, 125).unwrap(),
+            solve_with::<BitVec>(&wide, 125).unwrap()
+        );
+        let wider: Vec<_> = EXAMPLE.lines().map(|l| l.repeat(40)).collect();
+        assert!(solve(&wider.join("\n"), &Params::new(&[])).is_ok());
+
+        // Stray spaces are fine
+        let answers = solve(&format!(" {}\n", EXAMPLE), &Params::new(&[])).unwrap();
+        assert_eq!("230", answers.part_2);
+
+        let error = solve("0101\n011\n", &Params::new(&[])).unwrap_err();
+        assert_eq!(
+            "line 2, column 4: expected 4 bits like the first line, found 3",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,355 @@
+// Ratings pick one number out of the report by whittling the candidates down a bit at a time,
+// keeping the numbers with the most (or least) common value in each position. Every decision is
+// recorded so it's clear why a number was picked.
+//
+//     cargo run --bin day_3 -- --ratings
+//     cargo run --bin day_3 -- --ratings report.txt --criterion least --tie 1 --order lsb
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::cli::Args;
+
+use crate::bits::{BitVec, Bits};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    MostCommon,
+    LeastCommon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    MostSignificantFirst,
+    LeastSignificantFirst,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rating {
+    pub name: &'static str,
+    pub criterion: Criterion,
+    /// The value kept when zeros and ones are equally common.
+    pub tie: bool,
+    pub order: Order,
+}
+
+pub const OXYGEN_GENERATOR: Rating = Rating {
+    name: "oxygen generator",
+    criterion: Criterion::MostCommon,
+    tie: true,
+    order: Order::MostSignificantFirst,
+};
+
+pub const CO2_SCRUBBER: Rating = Rating {
+    name: "CO2 scrubber",
+    criterion: Criterion::LeastCommon,
+    tie: false,
+    order: Order::MostSignificantFirst,
+};
+
+/// One bit position's worth of whittling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub bit: usize,
+    pub zeros: usize,
+    pub ones: usize,
+    pub kept: bool,
+    /// How many candidates were left afterwards.
+    pub survivors: usize,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bit {}: {} zeros, {} ones, kept {}, {} left",
+            self.bit,
+            self.zeros,
+            self.ones,
+            u8::from(self.kept),
+            self.survivors
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection<B> {
+    pub value: B,
+    pub trace: Vec<Step>,
+}
+
+impl Rating {
+    /// The value to keep given how common each one is. A value that doesn't appear at all isn't
+    /// the least common, so when every candidate agrees they're all kept.
+    pub fn keep(&self, zeros: usize, ones: usize) -> bool {
+        if zeros == 0 || ones == 0 {
+            return ones > 0;
+        }
+        if zeros == ones {
+            return self.tie;
+        }
+        match self.criterion {
+            Criterion::MostCommon => ones > zeros,
+            Criterion::LeastCommon => ones < zeros,
+        }
+    }
+
+    /// The bit positions in the order they're considered.
+    pub fn bits(&self, width: usize) -> Vec<usize> {
+        match self.order {
+            Order::MostSignificantFirst => (0..width).rev().collect(),
+            Order::LeastSignificantFirst => (0..width).collect(),
+        }
+    }
+
+    /// Whittle `numbers` down to the one this rating picks, stopping as soon as there's only one
+    /// left.
+    pub fn select<B: Bits>(&self, numbers: &[B], width: usize) -> Result<Selection<B>, String> {
+        self.select_in(&Index::new(numbers.to_vec(), width, self.order))
+    }
+
+    /// `select` from numbers already sorted into an `Index` with the same order.
+    ///
+    /// Sorted that way the candidates left after each bit are a run of the index, with the ones
+    /// that have a 0 in the next bit considered before the ones with a 1. So each bit takes a
+    /// binary search for where the 1s start rather than a pass over the candidates.
+    pub fn select_in<B: Bits>(&self, index: &Index<B>) -> Result<Selection<B>, String> {
+        assert_eq!(self.order, index.order, "the index is in the wrong order");
+        let width = index.width;
+        let (mut start, mut end) = (0, index.sorted.len());
+        let mut trace = Vec::new();
+        // Bits of the index's keys, most significant first
+        for key_bit in (0..width).rev() {
+            if end - start <= 1 {
+                break;
+            }
+            let split = start + index.sorted[start..end].partition_point(|n| !n.bit(key_bit));
+            let (zeros, ones) = (split - start, end - split);
+            let kept = self.keep(zeros, ones);
+            if kept {
+                start = split;
+            } else {
+                end = split;
+            }
+            trace.push(Step {
+                bit: index.bit(key_bit),
+                zeros,
+                ones,
+                kept,
+                survivors: end - start,
+            });
+        }
+
+        match end - start {
+            1 => Ok(Selection {
+                value: index.number(start),
+                trace,
+            }),
+            0 => Err(format!("no numbers are left for the {} rating", self.name)),
+            n => Err(format!(
+                "{} equal numbers are left for the {} rating",
+                n, self.name
+            )),
+        }
+    }
+}
+
+/// Numbers sorted for ratings that consider bits in `order`, i.e. by value when that's most
+/// significant first, and by their reversed bits otherwise.
+#[derive(Debug, Clone)]
+pub struct Index<B> {
+    pub width: usize,
+    pub order: Order,
+    sorted: Vec<B>,
+}
+
+impl<B: Bits> Index<B> {
+    pub fn new(mut numbers: Vec<B>, width: usize, order: Order) -> Self {
+        if order == Order::LeastSignificantFirst {
+            for n in &mut numbers {
+                *n = n.reverse(width);
+            }
+        }
+        B::sort(&mut numbers, width);
+        Self {
+            width,
+            order,
+            sorted: numbers,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    // The number's bit for bit `i` of the sorted keys
+    fn bit(&self, i: usize) -> usize {
+        match self.order {
+            Order::MostSignificantFirst => i,
+            Order::LeastSignificantFirst => self.width - 1 - i,
+        }
+    }
+
+    /// The `i`th number in the index.
+    pub fn number(&self, i: usize) -> B {
+        match self.order {
+            Order::MostSignificantFirst => self.sorted[i].clone(),
+            Order::LeastSignificantFirst => self.sorted[i].reverse(self.width),
+        }
+    }
+}
+
+impl FromStr for Criterion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "most" => Ok(Criterion::MostCommon),
+            "least" => Ok(Criterion::LeastCommon),
+            _ => Err(format!("unknown criterion `{}`, expected most or least", s)),
+        }
+    }
+}
+
+impl FromStr for Order {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "msb" => Ok(Order::MostSignificantFirst),
+            "lsb" => Ok(Order::LeastSignificantFirst),
+            _ => Err(format!("unknown order `{}`, expected msb or lsb", s)),
+        }
+    }
+}
+
+/// `day_3 --ratings`, showing how each rating was picked from the bundled input unless given a
+/// file. Any of `--criterion`, `--tie` or `--order` define a custom rating instead.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut rating = Rating {
+        name: "custom",
+        ..OXYGEN_GENERATOR
+    };
+    let mut custom = false;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--criterion" | "-c" => rating.criterion = args.value(&arg)?.parse()?,
+            "--tie" | "-t" => {
+                rating.tie = match args.value(&arg)?.as_str() {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err("--tie needs 0 or 1".into()),
+                }
+            }
+            "--order" | "-o" => rating.order = args.value(&arg)?.parse()?,
+            _ => {
+                args.path(arg)?;
+                continue;
+            }
+        }
+        custom = true;
+    }
+    let ratings = if custom {
+        vec![rating]
+    } else {
+        vec![OXYGEN_GENERATOR, CO2_SCRUBBER]
+    };
+
+    let input = args.input(crate::DAY.input)?;
+    let width = crate::width(&input)?;
+    if width <= 64 {
+        show::<u64>(&ratings, &input, width)
+    } else if width <= 128 {
+        show::<u128>(&ratings, &input, width)
+    } else {
+        show::<BitVec>(&ratings, &input, width)
+    }
+}
+
+fn show<B: Bits>(ratings: &[Rating], input: &str, width: usize) -> aoc::Result<()> {
+    let numbers: Vec<B> = crate::parse(input, width)?;
+    for rating in ratings {
+        let selection = rating.select(&numbers, width)?;
+        let digits: String = (0..width)
+            .rev()
+            .map(|i| if selection.value.bit(i) { '1' } else { '0' })
+            .collect();
+        println!(
+            "{} rating: {} ({}), from {} numbers",
+            rating.name,
+            selection.value.value(width),
+            digits,
+            numbers.len()
+        );
+        for step in &selection.trace {
+            println!("    {}", step);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let numbers: Vec<u128> = crate::parse(crate::tests::EXAMPLE, 5).unwrap();
+        let width = 5;
+
+        let oxygen = OXYGEN_GENERATOR.select(&numbers, width).unwrap();
+        assert_eq!(0b10111, oxygen.value);
+        assert_eq!(
+            vec![
+                (4, true, 7),
+                (3, false, 4),
+                (2, true, 3),
+                (1, true, 2),
+                (0, true, 1)
+            ],
+            oxygen
+                .trace
+                .iter()
+                .map(|s| (s.bit, s.kept, s.survivors))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "bit 4: 5 zeros, 7 ones, kept 1, 7 left",
+            oxygen.trace[0].to_string()
+        );
+
+        let co2 = CO2_SCRUBBER.select(&numbers, width).unwrap();
+        assert_eq!((0b01010, 3), (co2.value, co2.trace.len()));
+
+        // Least significant bit first, most common, ties keep 0
+        let rating = Rating {
+            name: "test",
+            criterion: Criterion::MostCommon,
+            tie: false,
+            order: Order::LeastSignificantFirst,
+        };
+        let selection = rating.select(&numbers, width).unwrap();
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            selection.trace.iter().map(|s| s.bit).collect::<Vec<_>>()
+        );
+        assert_eq!(0b00010, selection.value);
+
+        // Nothing is thrown away on a bit they all share
+        let co2 = CO2_SCRUBBER.select(&[0b01u128, 0b00, 0b11], 2).unwrap();
+        assert_eq!((0b11, 1), (co2.value, co2.trace.len()));
+        let co2 = CO2_SCRUBBER.select(&[0b00u128, 0b01, 0b01], 2).unwrap();
+        assert_eq!((0b00, 2), (co2.value, co2.trace.len()));
+
+        assert_eq!(
+            Err("2 equal numbers are left for the test rating".to_string()),
+            rating.select(&[1u128, 1], 1).map(|s| s.value)
+        );
+    }
+}
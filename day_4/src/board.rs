@@ -0,0 +1,278 @@
+// Bingo boards of any size, the ways they can win and how they're scored.
+
+use std::fmt;
+use std::str::FromStr;
+
+use aoc::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub marked: bool,
+}
+
+impl Number {
+    pub fn new(value: u32) -> Self {
+        Number {
+            value,
+            marked: false,
+        }
+    }
+
+    fn unmarked_value(&self) -> Option<u64> {
+        if self.marked {
+            None
+        } else {
+            Some(u64::from(self.value))
+        }
+    }
+}
+
+// Marked numbers are starred
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let star = if self.marked { "*" } else { " " };
+        write!(f, "{:>3}{}", self.value, star)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    pub numbers: Grid<Number>,
+}
+
+impl Board {
+    pub fn new(numbers: Grid<Number>) -> Self {
+        Self { numbers }
+    }
+
+    pub fn width(&self) -> usize {
+        self.numbers.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.numbers.height()
+    }
+
+    /// Where `value` is on the board, if it is.
+    pub fn find(&self, value: u32) -> Option<(usize, usize)> {
+        self.numbers
+            .positions()
+            .zip(self.numbers.iter())
+            .find(|(_, n)| n.value == value)
+            .map(|(position, _)| position)
+    }
+
+    /// Mark `value` if it's on the board, returning whether it was.
+    pub fn mark(&mut self, value: u32) -> bool {
+        match self.numbers.iter_mut().find(|n| n.value == value) {
+            Some(n) => {
+                n.marked = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn marked(&self, x: usize, y: usize) -> bool {
+        self.numbers[(x, y)].marked
+    }
+
+    /// Whether any of `rules` is met.
+    pub fn finished(&self, rules: &[Rule]) -> bool {
+        rules.iter().any(|rule| rule.met(self))
+    }
+
+    /// The board's score having just won on `winning_number`.
+    pub fn score(&self, winning_number: u32, score: &dyn Score) -> u128 {
+        score.score(self, winning_number)
+    }
+
+    pub fn unmarked_sum(&self) -> u64 {
+        self.numbers.iter().filter_map(|n| n.unmarked_value()).sum()
+    }
+
+    pub fn marked_sum(&self) -> u64 {
+        self.numbers
+            .iter()
+            .filter(|n| n.marked)
+            .map(|n| u64::from(n.value))
+            .sum()
+    }
+
+    // The rows then the columns, as the cells in each
+    fn lines(&self) -> impl Iterator<Item = Vec<(usize, usize)>> + '_ {
+        let rows = (0..self.height()).map(move |y| (0..self.width()).map(|x| (x, y)).collect());
+        let columns = (0..self.width()).map(move |x| (0..self.height()).map(|y| (x, y)).collect());
+        rows.chain(columns)
+    }
+}
+
+/// A way for a board to win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Every number in a row.
+    Row,
+    Column,
+    /// Top left to bottom right, on a square board.
+    Diagonal,
+    /// Top right to bottom left, on a square board.
+    AntiDiagonal,
+    /// All four corners.
+    Corners,
+    /// Every number on the board.
+    FullCard,
+    /// At least this many numbers in a row or column.
+    InLine(usize),
+}
+
+impl Rule {
+    pub fn met(&self, board: &Board) -> bool {
+        let (width, height) = (board.width(), board.height());
+        let diagonal = |cell: &dyn Fn(usize) -> (usize, usize)| {
+            width == height && (0..width).all(|i| board.marked(cell(i).0, cell(i).1))
+        };
+        match *self {
+            Rule::Row => (0..height).any(|y| (0..width).all(|x| board.marked(x, y))),
+            Rule::Column => (0..width).any(|x| (0..height).all(|y| board.marked(x, y))),
+            Rule::Diagonal => diagonal(&|i| (i, i)),
+            Rule::AntiDiagonal => diagonal(&|i| (width - 1 - i, i)),
+            Rule::Corners => [
+                (0, 0),
+                (width - 1, 0),
+                (0, height - 1),
+                (width - 1, height - 1),
+            ]
+            .iter()
+            .all(|&(x, y)| board.marked(x, y)),
+            Rule::FullCard => board.numbers.iter().all(|n| n.marked),
+            Rule::InLine(k) => board
+                .lines()
+                .any(|line| line.iter().filter(|&&(x, y)| board.marked(x, y)).count() >= k),
+        }
+    }
+}
+
+/// Rules written like `row,column,line:4`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules(pub Vec<Rule>);
+
+impl FromStr for Rules {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        for rule in s.split(',').map(str::trim) {
+            match rule {
+                "row" | "rows" => rules.push(Rule::Row),
+                "column" | "columns" => rules.push(Rule::Column),
+                "diagonal" => rules.push(Rule::Diagonal),
+                "antidiagonal" => rules.push(Rule::AntiDiagonal),
+                "diagonals" => rules.extend_from_slice(&[Rule::Diagonal, Rule::AntiDiagonal]),
+                "corners" => rules.push(Rule::Corners),
+                "full" => rules.push(Rule::FullCard),
+                _ => match rule.strip_prefix("line:").map(str::parse) {
+                    Some(Ok(k)) if k > 0 => rules.push(Rule::InLine(k)),
+                    _ => {
+                        return Err(format!(
+                            "unknown rule `{}`, expected row, column, diagonal, antidiagonal, \
+                             diagonals, corners, full or line:K",
+                            rule
+                        ))
+                    }
+                },
+            }
+        }
+        Ok(Rules(rules))
+    }
+}
+
+/// How a board that's just won is scored. Scores are `u128`s as any size of board can have
+/// numbers up to `u32::MAX`, so a sum times the winning number needn't fit in a `u64`.
+pub trait Score {
+    fn score(&self, board: &Board, winning_number: u32) -> u128;
+}
+
+impl<F: Fn(&Board, u32) -> u128> Score for F {
+    fn score(&self, board: &Board, winning_number: u32) -> u128 {
+        self(board, winning_number)
+    }
+}
+
+/// The puzzle's score, the sum of the unmarked numbers times the winning number.
+pub fn unmarked(board: &Board, winning_number: u32) -> u128 {
+    u128::from(board.unmarked_sum()) * u128::from(winning_number)
+}
+
+/// The sum of the marked numbers times the winning number.
+pub fn marked(board: &Board, winning_number: u32) -> u128 {
+    u128::from(board.marked_sum()) * u128::from(winning_number)
+}
+
+/// A score that's just a function.
+pub type ScoreFn = fn(&Board, u32) -> u128;
+
+/// The scores that can be picked by name.
+pub const SCORES: [(&str, ScoreFn); 2] = [("unmarked", unmarked), ("marked", marked)];
+
+/// The score called `name`.
+pub fn score(name: &str) -> Result<ScoreFn, String> {
+    SCORES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|&(_, score)| score)
+        .ok_or_else(|| format!("unknown score `{}`, expected unmarked or marked", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(marked: &[(usize, usize)]) -> Board {
+        let mut numbers = Grid::new(3, 3, (1..=9).map(Number::new).collect());
+        for &cell in marked {
+            numbers[cell].marked = true;
+        }
+        Board::new(numbers)
+    }
+
+    #[test]
+    fn it_works() {
+        let rules: Rules = "rows,columns".parse().unwrap();
+        assert!(!board(&[(0, 0), (1, 1)]).finished(&rules.0));
+        assert!(board(&[(0, 1), (1, 1), (2, 1)]).finished(&rules.0));
+        assert!(board(&[(2, 0), (2, 1), (2, 2)]).finished(&rules.0));
+
+        let diagonal = board(&[(2, 0), (1, 1), (0, 2)]);
+        assert!(Rule::AntiDiagonal.met(&diagonal) && !Rule::Diagonal.met(&diagonal));
+        assert!(Rule::Corners.met(&board(&[(0, 0), (2, 0), (0, 2), (2, 2)])));
+        assert!(Rule::InLine(2).met(&board(&[(0, 0), (0, 2)])));
+        assert!(!Rule::InLine(2).met(&board(&[(0, 0), (1, 1)])));
+
+        let mut full = board(&[]);
+        for n in 1..=9 {
+            assert!(!Rule::FullCard.met(&full));
+            assert!(full.mark(n));
+        }
+        assert!(Rule::FullCard.met(&full) && !full.mark(10));
+
+        let row = board(&[(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(39 * 3, row.score(3, &unmarked));
+        assert_eq!(6 * 3, row.score(3, &score("marked").unwrap()));
+
+        // Too big for a `u64`
+        let big = Board::new(Grid::new(3, 3, vec![Number::new(u32::MAX); 9]));
+        let expected = 9 * u128::from(u32::MAX) * u128::from(u32::MAX);
+        assert_eq!(expected, big.score(u32::MAX, &unmarked));
+        assert_eq!(0, row.score(3, &|_: &Board, _| 0));
+
+        assert_eq!(
+            Err(
+                "unknown rule `line:0`, expected row, column, diagonal, antidiagonal, \
+                 diagonals, corners, full or line:K"
+                    .to_string()
+            ),
+            "row,line:0".parse::<Rules>()
+        );
+    }
+}
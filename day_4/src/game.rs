@@ -0,0 +1,314 @@
+// A record of a whole game: when every number on every board was marked, and when each board won
+// and with what score. Boards carry on being marked after they've won, so any board's state can
+// be looked up after any draw.
+//
+//     cargo run --bin day_4 -- --game
+//     cargo run --bin day_4 -- --game bingo.txt --rules full --board 3 --after 40
+//     cargo run --bin day_4 -- --game --json > game.json
+
+use std::fmt::Write;
+
+use aoc::cli::Args;
+
+use crate::board::{self, Board, Rule, Rules, Score};
+use crate::index::{Hits, Index};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Win {
+    pub board: usize,
+    /// The index of the winning draw.
+    pub draw: usize,
+    pub score: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub draws: Vec<u32>,
+    /// The boards before any numbers were drawn.
+    pub boards: Vec<Board>,
+    /// The index of the draw that marked each cell of each board, board by board.
+    marked_at: Vec<Option<usize>>,
+    /// Every mark as the board and cell index, in the order they were made, and where each
+    /// draw's marks start.
+    marks: Vec<(usize, usize)>,
+    draw_starts: Vec<usize>,
+    /// Every board that wins, in the order they won. Boards winning on the same draw are in the
+    /// order they're listed.
+    pub wins: Vec<Win>,
+    // Where each board is in `wins`
+    board_wins: Vec<Option<usize>>,
+}
+
+impl Game {
+    /// Play `draws` on `boards`, which must all be the same size. Each draw only touches the
+    /// boards it's on, and a board's win is found from the lines through the number marked, so
+    /// games with a great many boards don't take long.
+    pub fn play(draws: Vec<u32>, boards: Vec<Board>, rules: &[Rule], score: &dyn Score) -> Self {
+        let (width, height) = boards.first().map_or((1, 1), |b| (b.width(), b.height()));
+        assert!(
+            boards
+                .iter()
+                .all(|b| (b.width(), b.height()) == (width, height)),
+            "the boards aren't all the same size"
+        );
+        let cells = width * height;
+        let index = Index::new(&boards);
+        let mut hits = Hits::new(boards.len(), width, height, rules);
+        let mut marked_at = vec![None; boards.len() * cells];
+        let mut marks = Vec::new();
+        let mut draw_starts = Vec::with_capacity(draws.len());
+        let mut wins = Vec::new();
+        let mut board_wins = vec![None; boards.len()];
+
+        for (draw, &n) in draws.iter().enumerate() {
+            draw_starts.push(marks.len());
+            for &(b, cell) in index.get(n) {
+                if marked_at[b * cells + cell].is_some() {
+                    continue;
+                }
+                marked_at[b * cells + cell] = Some(draw);
+                marks.push((b, cell));
+                if hits.mark(b, cell) && board_wins[b].is_none() {
+                    board_wins[b] = Some(wins.len());
+                    let board = marked(&boards[b], &marked_at[b * cells..][..cells], draw);
+                    wins.push(Win {
+                        board: b,
+                        draw,
+                        score: board.score(n, score),
+                    });
+                }
+            }
+        }
+
+        Self {
+            draws,
+            boards,
+            marked_at,
+            marks,
+            draw_starts,
+            wins,
+            board_wins,
+        }
+    }
+
+    pub fn first(&self) -> Option<&Win> {
+        self.wins.first()
+    }
+
+    pub fn last(&self) -> Option<&Win> {
+        self.wins.last()
+    }
+
+    /// The boards that win on draw `k`.
+    pub fn winners_at(&self, k: usize) -> &[Win] {
+        let start = self.wins.partition_point(|w| w.draw < k);
+        let end = self.wins.partition_point(|w| w.draw <= k);
+        &self.wins[start..end]
+    }
+
+    /// How board `b` won, if it did.
+    pub fn win(&self, b: usize) -> Option<&Win> {
+        self.board_wins[b].map(|i| &self.wins[i])
+    }
+
+    /// Board `b` with the numbers drawn up to and including draw `k` marked.
+    pub fn board_after(&self, b: usize, k: usize) -> Board {
+        let cells = self.marked_at.len() / self.boards.len();
+        marked(&self.boards[b], &self.marked_at[b * cells..][..cells], k)
+    }
+
+    /// The cells marked on draw `k`, as the board and the cell's position.
+    pub fn marked_on(&self, k: usize) -> Vec<(usize, (usize, usize))> {
+        let end = self
+            .draw_starts
+            .get(k + 1)
+            .copied()
+            .unwrap_or(self.marks.len());
+        self.marks[self.draw_starts[k]..end]
+            .iter()
+            .map(|&(b, cell)| {
+                let width = self.boards[b].width();
+                (b, (cell % width, cell / width))
+            })
+            .collect()
+    }
+
+    /// Every board in the order they won, followed by any that never did.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ranking: Vec<_> = self.wins.iter().map(|w| w.board).collect();
+        let mut won = vec![false; self.boards.len()];
+        for &b in &ranking {
+            won[b] = true;
+        }
+        ranking.extend((0..self.boards.len()).filter(|&b| !won[b]));
+        ranking
+    }
+
+    /// The game as JSON: the draws, each board with its numbers and win, and a timeline of what
+    /// each draw marked and who it made win. Cells are `[x, y]` from the top left.
+    pub fn to_json(&self) -> String {
+        let list = |items: Vec<String>| format!("[{}]", items.join(", "));
+        let win = |w: Option<&Win>| match w {
+            Some(w) => format!("{{\"draw\": {}, \"score\": {}}}", w.draw, w.score),
+            None => "null".to_string(),
+        };
+
+        let mut json = String::new();
+        let draws = list(self.draws.iter().map(|n| n.to_string()).collect());
+        writeln!(json, "{{\n  \"draws\": {},\n  \"boards\": [", draws).unwrap();
+        for (b, board) in self.boards.iter().enumerate() {
+            let rows = board
+                .numbers
+                .rows()
+                .map(|row| list(row.iter().map(|n| n.value.to_string()).collect()))
+                .collect();
+            let comma = if b + 1 < self.boards.len() { "," } else { "" };
+            writeln!(
+                json,
+                "    {{\"board\": {}, \"numbers\": {}, \"win\": {}}}{}",
+                b,
+                list(rows),
+                win(self.win(b)),
+                comma
+            )
+            .unwrap();
+        }
+        writeln!(json, "  ],\n  \"timeline\": [").unwrap();
+        for (k, n) in self.draws.iter().enumerate() {
+            let marked = self
+                .marked_on(k)
+                .iter()
+                .map(|(b, (x, y))| format!("{{\"board\": {}, \"cell\": [{}, {}]}}", b, x, y))
+                .collect();
+            let wins = self
+                .winners_at(k)
+                .iter()
+                .map(|w| format!("{{\"board\": {}, \"score\": {}}}", w.board, w.score))
+                .collect();
+            let comma = if k + 1 < self.draws.len() { "," } else { "" };
+            writeln!(
+                json,
+                "    {{\"draw\": {}, \"number\": {}, \"marked\": {}, \"wins\": {}}}{}",
+                k,
+                n,
+                list(marked),
+                list(wins),
+                comma
+            )
+            .unwrap();
+        }
+        json.push_str("  ]\n}\n");
+        json
+    }
+}
+
+// `board` with the cells marked by draw `k` marked, going by when each cell was
+fn marked(board: &Board, marked_at: &[Option<usize>], k: usize) -> Board {
+    let mut board = board.clone();
+    for (number, marked_at) in board.numbers.iter_mut().zip(marked_at) {
+        number.marked = marked_at.is_some_and(|draw| draw <= k);
+    }
+    board
+}
+
+/// `day_4 --game`, playing the bundled input unless given a file and showing the order the boards
+/// win in. `--board` shows one board after `--after` draws instead, and `--json` the whole record.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut rules = Rules(vec![Rule::Row, Rule::Column]);
+    let mut score = board::unmarked as board::ScoreFn;
+    let mut json = false;
+    let mut board = None;
+    let mut after = None;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--rules" | "-r" => rules = args.value(&arg)?.parse()?,
+            "--score" | "-s" => score = board::score(&args.value(&arg)?)?,
+            "--json" => json = true,
+            "--board" | "-b" => board = Some(args.parse(&arg, "a board number")?),
+            "--after" | "-a" => after = Some(args.parse(&arg, "a draw number")?),
+            _ => args.path(arg)?,
+        }
+    }
+
+    let input = args.input(crate::DAY.input)?;
+    let (draws, boards) = crate::parse(&input)?;
+    let game = Game::play(draws, boards, &rules.0, &score);
+
+    if json {
+        print!("{}", game.to_json());
+        return Ok(());
+    }
+    if let Some(b) = board {
+        if b >= game.boards.len() {
+            return Err(format!("there are only {} boards", game.boards.len()).into());
+        }
+        let k = after.unwrap_or_else(|| game.draws.len().saturating_sub(1));
+        if k >= game.draws.len() {
+            return Err(format!("there are only {} draws", game.draws.len()).into());
+        }
+        println!("board {} after draw {} ({}):", b, k, game.draws[k]);
+        print!("{:?}", game.board_after(b, k).numbers);
+        return Ok(());
+    }
+
+    for (rank, b) in game.ranking().into_iter().enumerate() {
+        match game.win(b) {
+            Some(w) => println!(
+                "{:>4}. board {} wins on draw {} ({}), scoring {}",
+                rank + 1,
+                b,
+                w.draw,
+                game.draws[w.draw],
+                w.score
+            ),
+            None => println!("{:>4}. board {} never wins", rank + 1, b),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let (draws, boards) = crate::parse(crate::tests::EXAMPLE).unwrap();
+        let rules = [Rule::Row, Rule::Column];
+        let game = Game::play(draws, boards, &rules, &board::unmarked);
+
+        assert_eq!(
+            Some(&Win {
+                board: 2,
+                draw: 11,
+                score: 4512
+            }),
+            game.first()
+        );
+        assert_eq!(Some(1924), game.last().map(|w| w.score));
+        assert_eq!(vec![2, 0, 1], game.ranking());
+        assert_eq!(&game.wins[..1], game.winners_at(11));
+        assert!(game.winners_at(10).is_empty());
+
+        // The winning row, and not yet the number that completes it
+        let board = game.board_after(2, 11);
+        assert!((0..5).all(|x| board.marked(x, 0)) && board.finished(&rules));
+        assert!(!game.board_after(2, 10).finished(&rules));
+        assert_eq!(vec![(0, (0, 4))], game.marked_on(26));
+
+        let json = game.to_json();
+        assert!(json.starts_with("{\n  \"draws\": [7, 4, 9,"));
+        assert!(json.contains(
+            "{\"board\": 2, \"numbers\": [[14, 21, 17, 24, 4], [10, 16, 15, 9, 19], \
+             [18, 8, 23, 26, 20], [22, 11, 13, 6, 5], [2, 0, 12, 3, 7]], \
+             \"win\": {\"draw\": 11, \"score\": 4512}}\n"
+        ));
+        assert!(json.contains(
+            "{\"draw\": 11, \"number\": 24, \"marked\": [{\"board\": 0, \"cell\": [4, 1]}, \
+             {\"board\": 1, \"cell\": [3, 3]}, {\"board\": 2, \"cell\": [3, 0]}], \
+             \"wins\": [{\"board\": 2, \"score\": 4512}]},\n"
+        ));
+    }
+}
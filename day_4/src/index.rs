@@ -0,0 +1,158 @@
+// What a game needs to handle huge numbers of boards: where each number is on every board, so a
+// draw only touches the boards it's on, and how many marks each line of each board has, so a
+// board's win is spotted as soon as the mark that makes it happens.
+
+use std::collections::HashMap;
+
+use crate::board::{Board, Rule};
+
+/// Where every number is, as the board and the cell's index in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    cells: HashMap<u32, Vec<(usize, usize)>>,
+}
+
+impl Index {
+    pub fn new(boards: &[Board]) -> Self {
+        let mut cells: HashMap<u32, Vec<(usize, usize)>> = HashMap::new();
+        for (b, board) in boards.iter().enumerate() {
+            for (cell, n) in board.numbers.iter().enumerate() {
+                let places = cells.entry(n.value).or_default();
+                // Only the first of a number repeated on a board is marked, as with `Board::mark`
+                if places.last().map(|&(last, _)| last) != Some(b) {
+                    places.push((b, cell));
+                }
+            }
+        }
+        Self { cells }
+    }
+
+    /// The places `n` is on the boards, in board order.
+    pub fn get(&self, n: u32) -> &[(usize, usize)] {
+        self.cells.get(&n).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// How many marks there are in each row, column, diagonal and corner, and on the whole of each
+/// board, for boards that are all `width` by `height`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hits {
+    width: usize,
+    height: usize,
+    rules: Vec<Rule>,
+    // Each board's rows, then columns, then the diagonal, anti-diagonal, corners and total
+    counts: Vec<u32>,
+    corners: u32,
+}
+
+impl Hits {
+    pub fn new(boards: usize, width: usize, height: usize, rules: &[Rule]) -> Self {
+        let corners = [
+            (0, 0),
+            (width - 1, 0),
+            (0, height - 1),
+            (width - 1, height - 1),
+        ];
+        let mut distinct = corners.to_vec();
+        distinct.sort_unstable();
+        distinct.dedup();
+        Self {
+            width,
+            height,
+            rules: rules.to_vec(),
+            counts: vec![0; boards * (width + height + 4)],
+            corners: distinct.len() as u32,
+        }
+    }
+
+    /// Count a mark on `board` at cell index `cell`, which mustn't have been marked before,
+    /// returning whether it meets one of the rules. A board only needs checking when it's
+    /// marked, and then only the lines through the mark can have changed.
+    pub fn mark(&mut self, board: usize, cell: usize) -> bool {
+        let (width, height, all_corners) = (self.width, self.height, self.corners);
+        let (x, y) = (cell % width, cell / width);
+        let counts = &mut self.counts[board * (width + height + 4)..][..width + height + 4];
+        let (row, column) = (y, height + x);
+        let (diagonal, anti_diagonal) = (height + width, height + width + 1);
+        let (corners, total) = (height + width + 2, height + width + 3);
+
+        counts[row] += 1;
+        counts[column] += 1;
+        counts[total] += 1;
+        let square = width == height;
+        let on_diagonal = square && x == y;
+        let on_anti_diagonal = square && x == width - 1 - y;
+        if on_diagonal {
+            counts[diagonal] += 1;
+        }
+        if on_anti_diagonal {
+            counts[anti_diagonal] += 1;
+        }
+        let on_corner = (x == 0 || x == width - 1) && (y == 0 || y == height - 1);
+        if on_corner {
+            counts[corners] += 1;
+        }
+
+        let counts = &*counts;
+        let full = |i: usize, len: usize| counts[i] as usize == len;
+        self.rules.iter().any(|rule| match *rule {
+            Rule::Row => full(row, width),
+            Rule::Column => full(column, height),
+            Rule::Diagonal => on_diagonal && full(diagonal, width),
+            Rule::AntiDiagonal => on_anti_diagonal && full(anti_diagonal, width),
+            Rule::Corners => on_corner && counts[corners] == all_corners,
+            Rule::FullCard => full(total, width * height),
+            Rule::InLine(k) => counts[row] as usize >= k || counts[column] as usize >= k,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Number;
+    use aoc::grid::Grid;
+
+    #[test]
+    fn it_works() {
+        let boards: Vec<_> = (0..3)
+            .map(|b| {
+                Board::new(Grid::new(
+                    3,
+                    3,
+                    (0..9).map(|n| Number::new(n * (b + 1))).collect(),
+                ))
+            })
+            .collect();
+        let index = Index::new(&boards);
+        assert_eq!(&[(0, 6), (1, 3), (2, 2)], index.get(6));
+        assert_eq!(&[(0, 8), (1, 4)], index.get(8));
+        assert_eq!(&[(2, 3)], index.get(9));
+        assert!(index.get(100).is_empty());
+
+        // Every rule agrees with the board's own check, whatever order the cells are marked in
+        let rules = [
+            Rule::Row,
+            Rule::Column,
+            Rule::Diagonal,
+            Rule::AntiDiagonal,
+            Rule::Corners,
+            Rule::FullCard,
+            Rule::InLine(2),
+        ];
+        for &rule in &rules {
+            for step in [1, 2, 4, 5, 7] {
+                let mut board = boards[0].clone();
+                let mut hits = Hits::new(1, 3, 3, &[rule]);
+                let mut won = false;
+                for i in 0..9 {
+                    let cell = i * step % 9;
+                    let n = board.numbers.iter().nth(cell).unwrap().value;
+                    board.mark(n);
+                    won |= hits.mark(0, cell);
+                    assert_eq!(board.finished(&[rule]), won, "{:?} marking {}", rule, cell);
+                }
+            }
+        }
+    }
+}
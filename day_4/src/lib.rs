@@ -0,0 +1,123 @@
+use aoc::params::{Param, Params};
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+
+pub mod board;
+pub mod game;
+pub mod index;
+pub mod rig;
+
+use board::{Board, Number, Rules};
+use game::Game;
+
+pub const DAY: Day = Day {
+    name: "day_4",
+    input: include_str!("../input"),
+    params: &[
+        Param::new(
+            "rules",
+            "rows,columns",
+            "How a board wins, any of row, column, diagonal, antidiagonal, corners, full or line:K",
+        ),
+        Param::new(
+            "score",
+            "unmarked",
+            "How a winning board is scored, the unmarked or marked numbers times the last drawn",
+        ),
+    ],
+    answers: ["10374", "24742"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let (numbers, boards) = parse(input)?;
+    let Rules(rules) = params.get("rules")?;
+    let score = board::score(params.value("score")?)?;
+
+    let game = Game::play(numbers, boards, &rules, &score);
+    let part_1 = game.first().ok_or("no board wins")?.score;
+    let part_2 = game.last().ok_or("no board wins")?.score;
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+// The drawn numbers on the first line, then boards separated by blank lines. Boards can be any
+// size, as long as they're all the same.
+pub fn parse(input: &str) -> parse::Result<(Vec<u32>, Vec<Board>)> {
+    Parser::parse(input, |p| {
+        let numbers = p.section(|s| s.list(",", |n| n.integer()))?;
+        let mut size = None;
+        let boards = p.sections(|s| {
+            let start = *s;
+            let grid = s.word_grid(|n| n.integer().map(Number::new))?;
+            match size {
+                None => size = Some((grid.width(), grid.height())),
+                Some((width, height)) if (grid.width(), grid.height()) != (width, height) => {
+                    return Err(start.error(format!(
+                        "expected a {}x{} board like the first, found {}x{}",
+                        width,
+                        height,
+                        grid.width(),
+                        grid.height()
+                    )))
+                }
+                Some(_) => (),
+            }
+            Ok(Board::new(grid))
+        })?;
+        Ok((numbers, boards))
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub const EXAMPLE: &str =
+        "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+    #[test]
+    fn it_works() {
+        let mut params = Params::new(DAY.params);
+        let answers = solve(EXAMPLE, &params).unwrap();
+        assert_eq!(("4512", "1924"), (&*answers.part_1, &*answers.part_2));
+
+        params.set("rules", "full").unwrap();
+        params.set("score", "marked").unwrap();
+        assert!(solve(EXAMPLE, &params).is_ok());
+
+        let error = solve(
+            "1,2\n\n1 2\n3 4\n\n1 2 3\n4 5 6\n",
+            &Params::new(DAY.params),
+        );
+        assert_eq!(
+            "line 6, column 1: expected a 2x2 board like the first, found 3x2",
+            error.unwrap_err().to_string()
+        );
+
+        // 2x3 boards, both winning on a column with the last number
+        let input = "5,1,3\n\n1 2\n3 4\n5 6\n\n6 5\n4 3\n2 1\n";
+        let answers = solve(input, &Params::new(DAY.params)).unwrap();
+        assert_eq!(("36", "36"), (&*answers.part_1, &*answers.part_2));
+    }
+}
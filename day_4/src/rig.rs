@@ -0,0 +1,442 @@
+// Rigging a game: the fewest numbers, drawn from a pool, that make one board win before all the
+// others or after all the others. Winning is a matter of which numbers are drawn rather than
+// their order, so the search is over sets of numbers, built from the smallest sets that complete
+// each way a board can win. Whatever's found is replayed on the boards to check it.
+//
+// Winning first is quick, but winning last means finishing every other board as cheaply as
+// possible, which is a covering problem. That's fine for the example's few boards, but with the
+// puzzle's hundred the search can't rule out shorter draws before `MAX_STATES`, taking several
+// seconds to give up with the best it found. A `--pool` of fewer numbers narrows it down.
+//
+//     cargo run --bin day_4 -- --rig 12
+//     cargo run --bin day_4 -- --rig 12 bingo.txt --last --pool 1,2,3,4,5 --rules line:3
+
+use std::collections::{HashMap, HashSet};
+
+use aoc::cli::Args;
+
+use crate::board::{Board, Rule, Rules};
+
+/// Searching for a board to win last gives up after this many sets of numbers, around ten seconds'
+/// worth with the puzzle's boards.
+const MAX_STATES: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// Win with no other board having won at the same time or before.
+    First,
+    /// Win on the last draw, after every other board has won.
+    Last,
+}
+
+// Sets of the pool's numbers, by their index in the pool
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Set(Vec<u64>);
+
+impl Set {
+    fn new(len: usize) -> Self {
+        Set(vec![0; len.div_ceil(64)])
+    }
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.0[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn is_subset(&self, other: &Set) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & !b == 0)
+    }
+
+    // How many of these aren't in `other`
+    fn missing_from(&self, other: &Set) -> usize {
+        let missing = self.0.iter().zip(&other.0).map(|(a, b)| a & !b);
+        missing.map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn union(&self, other: &Set) -> Set {
+        Set(self.0.iter().zip(&other.0).map(|(a, b)| a | b).collect())
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.0.len() * 64).filter(move |&i| self.contains(i))
+    }
+}
+
+/// The smallest sets of cells, as indexes into the board, that meet each of `rules`.
+pub fn patterns(board: &Board, rules: &[Rule]) -> Vec<Vec<usize>> {
+    let (width, height) = (board.width(), board.height());
+    let cell = |x: usize, y: usize| y * width + x;
+    let rows: Vec<Vec<usize>> = (0..height)
+        .map(|y| (0..width).map(|x| cell(x, y)).collect())
+        .collect();
+    let columns: Vec<Vec<usize>> = (0..width)
+        .map(|x| (0..height).map(|y| cell(x, y)).collect())
+        .collect();
+
+    let mut patterns = Vec::new();
+    for rule in rules {
+        match *rule {
+            Rule::Row => patterns.extend(rows.iter().cloned()),
+            Rule::Column => patterns.extend(columns.iter().cloned()),
+            Rule::Diagonal if width == height => {
+                patterns.push((0..width).map(|i| cell(i, i)).collect())
+            }
+            Rule::AntiDiagonal if width == height => {
+                patterns.push((0..width).map(|i| cell(width - 1 - i, i)).collect())
+            }
+            Rule::Diagonal | Rule::AntiDiagonal => (),
+            Rule::Corners => {
+                let mut corners = vec![
+                    cell(0, 0),
+                    cell(width - 1, 0),
+                    cell(0, height - 1),
+                    cell(width - 1, height - 1),
+                ];
+                corners.sort_unstable();
+                corners.dedup();
+                patterns.push(corners);
+            }
+            Rule::FullCard => patterns.push((0..width * height).collect()),
+            Rule::InLine(k) => {
+                for line in rows.iter().chain(&columns) {
+                    patterns.extend(choose(line, k));
+                }
+            }
+        }
+    }
+    patterns
+}
+
+// Every way of choosing `k` of `items`, in order
+fn choose(items: &[usize], k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    if items.len() < k {
+        return Vec::new();
+    }
+    let mut chosen = choose(&items[1..], k - 1);
+    for c in &mut chosen {
+        c.insert(0, items[0]);
+    }
+    chosen.extend(choose(&items[1..], k));
+    chosen
+}
+
+// The ways each board can win as sets of the pool's numbers, leaving out any that need a number
+// that isn't in the pool, or the second of a number repeated on a board as that's never marked
+fn win_sets(board: &Board, rules: &[Rule], pool: &HashMap<u32, usize>) -> Vec<Set> {
+    let values: Vec<u32> = board.numbers.iter().map(|n| n.value).collect();
+    let markable = |cell: usize| !values[..cell].contains(&values[cell]);
+    let mut sets: Vec<Set> = patterns(board, rules)
+        .into_iter()
+        .filter(|p| p.iter().all(|&c| markable(c)))
+        .filter_map(|p| {
+            let mut set = Set::new(pool.len());
+            for c in p {
+                set.insert(*pool.get(&values[c])?);
+            }
+            Some(set)
+        })
+        .collect();
+    sets.sort_by_key(|s| s.len());
+    sets.dedup();
+    sets
+}
+
+/// The fewest numbers from `pool`, in the order to draw them, that make board `target` meet its
+/// goal under `rules`. `Ok(None)` means it can't be done, and an error that the search gave up, or
+/// that what it found didn't check out.
+pub fn rig(
+    boards: &[Board],
+    target: usize,
+    goal: Goal,
+    pool: &[u32],
+    rules: &[Rule],
+) -> Result<Option<Vec<u32>>, String> {
+    let mut numbers = pool.to_vec();
+    numbers.sort_unstable();
+    numbers.dedup();
+    let index: HashMap<u32, usize> = numbers.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let sets: Vec<Vec<Set>> = boards.iter().map(|b| win_sets(b, rules, &index)).collect();
+
+    let found = match goal {
+        Goal::First => first(&sets, target),
+        Goal::Last => Last::new(&sets, target).search()?,
+    };
+    let draws = found.map(|(set, last)| {
+        let mut draws: Vec<u32> = set
+            .iter()
+            .filter(|&i| Some(i) != last)
+            .map(|i| numbers[i])
+            .collect();
+        draws.extend(last.map(|i| numbers[i]));
+        draws
+    });
+    match draws {
+        Some(draws) if !verify(boards, target, goal, &draws, rules) => {
+            Err(format!("the draws found don't rig the game: {:?}", draws))
+        }
+        draws => Ok(draws),
+    }
+}
+
+// Whether any of the ways to win is in `set`
+fn wins(sets: &[Set], set: &Set) -> bool {
+    sets.iter().any(|s| s.is_subset(set))
+}
+
+// The target's smallest way to win that doesn't let another board win too. Drawing a superset of
+// it couldn't help, as it'd only bring the others closer.
+fn first(sets: &[Vec<Set>], target: usize) -> Option<(Set, Option<usize>)> {
+    sets[target]
+        .iter()
+        .find(|&set| {
+            let mut others = sets.iter().enumerate().filter(|&(b, _)| b != target);
+            others.all(|(_, other)| !wins(other, set))
+        })
+        .map(|set| (set.clone(), None))
+}
+
+// Searching for numbers that finish every other board without finishing the target, then the
+// fewest more that finish the target, the last of which only the target needs. Branch and bound
+// over the ways to finish whichever board has the fewest left.
+struct Last<'a> {
+    sets: &'a [Vec<Set>],
+    target: usize,
+    best: Option<(Set, Option<usize>)>,
+    // Every set of numbers already searched
+    seen: HashSet<Set>,
+}
+
+impl<'a> Last<'a> {
+    fn new(sets: &'a [Vec<Set>], target: usize) -> Self {
+        Self {
+            sets,
+            target,
+            best: None,
+            seen: HashSet::new(),
+        }
+    }
+
+    fn search(mut self) -> Result<Option<(Set, Option<usize>)>, String> {
+        let pool = self.sets.iter().flatten().map(|s| s.0.len()).max();
+        if let Some(words) = pool {
+            self.extend(Set(vec![0; words]))?;
+        }
+        Ok(self.best)
+    }
+
+    fn best_len(&self) -> usize {
+        self.best.as_ref().map_or(usize::MAX, |(set, _)| set.len())
+    }
+
+    fn extend(&mut self, drawn: Set) -> Result<(), String> {
+        if !self.seen.insert(drawn.clone()) {
+            return Ok(());
+        }
+        if self.seen.len() > MAX_STATES {
+            return Err(match &self.best {
+                Some((set, _)) => format!(
+                    "gave up after {} states, with the best so far {} draws, try a smaller --pool",
+                    MAX_STATES,
+                    set.len()
+                ),
+                None => format!("gave up after {} states, try a smaller --pool", MAX_STATES),
+            });
+        }
+        let target = &self.sets[self.target];
+        let target_needs = match target.iter().map(|s| s.missing_from(&drawn)).min() {
+            Some(0) | None => return Ok(()),
+            Some(needed) => needed,
+        };
+
+        // Each number drawn brings each board still to win at most one closer, and only if it's
+        // in one of the board's ways to win. So however many more numbers the boards need
+        // between them, at least enough of the most useful numbers to cover that are needed.
+        let mut needs = 0;
+        let mut useful = vec![0; drawn.0.len() * 64];
+        let mut next: Option<&[Set]> = None;
+        for (b, sets) in self.sets.iter().enumerate() {
+            if b == self.target || wins(sets, &drawn) {
+                continue;
+            }
+            match sets.iter().map(|s| s.missing_from(&drawn)).min() {
+                Some(needed) => needs += needed,
+                None => return Ok(()),
+            }
+            let mut numbers = Set(vec![0; drawn.0.len()]);
+            for set in sets {
+                numbers = numbers.union(set);
+            }
+            for i in numbers.iter().filter(|&i| !drawn.contains(i)) {
+                useful[i] += 1;
+            }
+            if next.is_none_or(|n| sets.len() < n.len()) {
+                next = Some(sets);
+            }
+        }
+        useful.sort_unstable_by(|a, b| b.cmp(a));
+        let mut more = 0;
+        let mut covered = 0;
+        while covered < needs {
+            covered += useful[more];
+            more += 1;
+        }
+        let bound = drawn.len() + (more + 1).max(target_needs);
+        if bound >= self.best_len() {
+            return Ok(());
+        }
+
+        match next {
+            // Every other board has won, so finish the target the quickest way
+            None => {
+                let way = target
+                    .iter()
+                    .min_by_key(|s| s.missing_from(&drawn))
+                    .unwrap();
+                let set = drawn.union(way);
+                let last = way.iter().find(|&i| !drawn.contains(i));
+                self.best = Some((set, last));
+            }
+            Some(ways) => {
+                let mut ways: Vec<&Set> = ways.iter().collect();
+                ways.sort_by_key(|s| s.missing_from(&drawn));
+                for way in ways {
+                    self.extend(drawn.union(way))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether drawing `draws` on `boards` makes `target` meet its goal, going by the boards' own
+/// marking and win checks.
+pub fn verify(boards: &[Board], target: usize, goal: Goal, draws: &[u32], rules: &[Rule]) -> bool {
+    let mut boards = boards.to_vec();
+    let mut won_at = vec![None; boards.len()];
+    for (draw, &n) in draws.iter().enumerate() {
+        for (b, board) in boards.iter_mut().enumerate() {
+            board.mark(n);
+            if won_at[b].is_none() && board.finished(rules) {
+                won_at[b] = Some(draw);
+            }
+        }
+    }
+    let others = || won_at.iter().enumerate().filter(|&(b, _)| b != target);
+    match (goal, won_at[target]) {
+        (Goal::First, Some(won)) => others().all(|(_, w)| w.is_none_or(|w| w > won)),
+        (Goal::Last, Some(won)) => {
+            won + 1 == draws.len() && others().all(|(_, w)| w.is_some_and(|w| w < won))
+        }
+        (_, None) => false,
+    }
+}
+
+/// `day_4 --rig BOARD`, finding the fewest numbers that make a board from the bundled input, or
+/// a file, win first or with `--last` last. The numbers come from the input's draws unless
+/// there's a `--pool`. Winning last among many boards can give up, see `MAX_STATES`.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut args = Args::new(args);
+    let target: usize = args.parse("--rig", "a board number")?;
+    let mut goal = Goal::First;
+    let mut pool = None;
+    let mut rules = Rules(vec![Rule::Row, Rule::Column]);
+
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--last" => goal = Goal::Last,
+            "--pool" | "-p" => {
+                let numbers: Result<Vec<u32>, _> =
+                    args.value(&arg)?.split(',').map(str::parse).collect();
+                pool = Some(numbers.map_err(|_| "--pool needs numbers like 1,2,3")?);
+            }
+            "--rules" | "-r" => rules = args.value(&arg)?.parse()?,
+            _ => args.path(arg)?,
+        }
+    }
+
+    let input = args.input(crate::DAY.input)?;
+    let (draws, boards) = crate::parse(&input)?;
+    if target >= boards.len() {
+        return Err(format!("there are only {} boards", boards.len()).into());
+    }
+    let pool = pool.unwrap_or(draws);
+
+    let order = match goal {
+        Goal::First => "first",
+        Goal::Last => "last",
+    };
+    match rig(&boards, target, goal, &pool, &rules.0)? {
+        Some(draws) => {
+            let draws: Vec<_> = draws.iter().map(|n| n.to_string()).collect();
+            println!("{}", draws.join(","));
+            eprintln!(
+                "board {} wins {} after {} draws",
+                target,
+                order,
+                draws.len()
+            );
+            Ok(())
+        }
+        None => Err(format!("board {} can't win {} with these numbers", target, order).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let (draws, boards) = crate::parse(crate::tests::EXAMPLE).unwrap();
+        let rules = [Rule::Row, Rule::Column];
+
+        for target in 0..3 {
+            let first = rig(&boards, target, Goal::First, &draws, &rules).unwrap();
+            assert_eq!(Some(5), first.map(|d| d.len()));
+        }
+        let last = rig(&boards, 0, Goal::Last, &draws, &rules)
+            .unwrap()
+            .unwrap();
+        assert!(verify(&boards, 0, Goal::Last, &last, &rules));
+        assert!(!verify(&boards, 1, Goal::Last, &last, &rules));
+
+        // Board 1's first row is board 0's too
+        let board = crate::parse("1\n\n1 2\n3 4\n\n1 2\n3 5\n").unwrap().1;
+        let pool = [1, 2, 3, 4, 5];
+        assert_eq!(
+            Ok(Some(vec![3, 5])),
+            rig(&board, 1, Goal::First, &pool, &rules)
+        );
+        assert_eq!(
+            Ok(None),
+            rig(&board, 1, Goal::First, &[1, 3, 5], &[Rule::Column])
+        );
+        assert_eq!(
+            Ok(Some(vec![2, 5, 4])),
+            rig(&board, 0, Goal::Last, &pool, &[Rule::Column])
+        );
+        assert_eq!(
+            Ok(Some(vec![1, 2, 3, 5, 4])),
+            rig(&board, 0, Goal::Last, &pool, &[Rule::FullCard])
+        );
+        assert_eq!(
+            Ok(None),
+            rig(&board, 0, Goal::Last, &pool[..4], &[Rule::FullCard])
+        );
+
+        assert_eq!(
+            vec![vec![0, 1], vec![0, 2], vec![1, 2]],
+            choose(&[0, 1, 2], 2)
+        );
+    }
+}
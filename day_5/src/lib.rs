@@ -0,0 +1,216 @@
+use aoc::params::{Param, Params};
+use aoc::parse::{self, Parser};
+use aoc::{Alternative, Answers, Day};
+
+pub mod map;
+pub mod query;
+pub mod raster;
+pub mod sweep;
+
+use map::{Backend, Bounds, Dense, Map, Sparse};
+use raster::Raster;
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Point {
+    pub fn new(x: u32, y: u32) -> Self {
+        Self { x, y }
+    }
+}
+
+pub const DAY: Day = Day {
+    name: "day_5",
+    input: include_str!("../input"),
+    params: &[
+        Param::new(
+            "map",
+            "auto",
+            "Where overlaps are counted: a dense grid, a sparse hash map, or auto to pick by density",
+        ),
+        Param::new(
+            "raster",
+            "strict",
+            "Which cells a line covers: strict for only the puzzle's lines, bresenham, supercover \
+             or lattice",
+        ),
+    ],
+    answers: ["7436", "21104"],
+    solve,
+    alternatives: &[Alternative {
+        name: "sweep",
+        solve: solve_sweep,
+    }],
+};
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let hydrothermal_vents_positions = parse(input)?;
+    let backend = params.get::<Backend>("map")?;
+    let raster = params.get::<Raster>("raster")?;
+    raster.check(&hydrothermal_vents_positions)?;
+
+    let part_1 = overlaps(&hydrothermal_vents_positions, false, backend, raster)?;
+    let part_2 = overlaps(&hydrothermal_vents_positions, true, backend, raster)?;
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+// Counting without rasterising, see `sweep`. Only the puzzle's lines can be swept, and they
+// cover the same cells under every raster but supercover.
+fn solve_sweep(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let hydrothermal_vents_positions = parse(input)?;
+    if params.get::<Raster>("raster")? == Raster::Supercover {
+        return Err("can't sweep supercover lines".into());
+    }
+
+    let part_1 = sweep::overlaps(&hydrothermal_vents_positions, false)?;
+    let part_2 = sweep::overlaps(&hydrothermal_vents_positions, true)?;
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+/// How many cells more than one of `lines` covers, which `raster` must support. An error if the
+/// dense map was asked for and the lines' bounds are too big for it.
+pub fn overlaps(
+    lines: &[(Point, Point)],
+    diagonals: bool,
+    backend: Backend,
+    raster: Raster,
+) -> Result<usize, String> {
+    match (backend.resolve(lines), Bounds::of(lines)) {
+        (Backend::Dense, Some(bounds)) if !bounds.fits_dense() => Err(format!(
+            "the lines cover {}x{} cells, too many for a dense map, try map=sparse",
+            bounds.width(),
+            bounds.height()
+        )),
+        (Backend::Dense, Some(bounds)) => {
+            let threads = map::dense_threads(bounds);
+            let map = map::rasterise(lines, diagonals, raster, threads, || Dense::new(bounds));
+            Ok(map.overlaps())
+        }
+        _ => {
+            let threads = map::sparse_threads();
+            let map = map::rasterise(lines, diagonals, raster, threads, Sparse::default);
+            Ok(map.overlaps())
+        }
+    }
+}
+
+pub fn parse(input: &str) -> parse::Result<Vec<(Point, Point)>> {
+    fn point(p: &mut Parser) -> parse::Result<Point> {
+        let x = p.integer()?;
+        p.tag(",")?;
+        Ok(Point::new(x, p.integer()?))
+    }
+
+    Parser::parse(input, |p| {
+        p.lines(|line| {
+            // x1,y1 -> x2,y2
+            let p1 = point(line)?;
+            line.tag(" -> ")?;
+            Ok((p1, point(line)?))
+        })
+    })
+}
+
+/// Add the cells from `p1` to `p2` to `map`, as long as the line is horizontal or vertical or
+/// `draw_diagonals`. Lines at any other angle are diagonal.
+pub fn line(map: &mut impl Map, draw_diagonals: bool, raster: Raster, p1: Point, p2: Point) {
+    cells(draw_diagonals, raster, p1, p2, |p| map.add(p));
+}
+
+/// Call `f` with each cell `line` would add.
+pub fn cells(draw_diagonals: bool, raster: Raster, p1: Point, p2: Point, mut f: impl FnMut(Point)) {
+    if p1.x == p2.x {
+        for y in p1.y.min(p2.y)..=p1.y.max(p2.y) {
+            f(Point::new(p1.x, y));
+        }
+    } else if p1.y == p2.y {
+        for x in p1.x.min(p2.x)..=p1.x.max(p2.x) {
+            f(Point::new(x, p1.y));
+        }
+    } else if draw_diagonals {
+        raster.cells(p1, p2, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let example = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+        let hydrothermal_vents_positions = parse(example).unwrap();
+
+        let mut map_1 = Sparse::default();
+        let mut map_2 = Sparse::default();
+
+        for (p1, p2) in hydrothermal_vents_positions {
+            line(&mut map_1, false, Raster::Strict, p1, p2);
+            line(&mut map_2, true, Raster::Strict, p1, p2);
+        }
+
+        print_map(&map_1);
+        println!("----");
+        print_map(&map_2);
+        println!("----");
+
+        assert_eq!(5, map_1.overlaps());
+        assert_eq!(12, map_2.overlaps());
+
+        let mut params = Params::new(DAY.params);
+        for backend in ["auto", "sparse", "dense"] {
+            params.set("map", backend).unwrap();
+            let answers = solve(example, &params).unwrap();
+            assert_eq!(("5", "12"), (&*answers.part_1, &*answers.part_2));
+        }
+
+        // Far beyond 16 bits
+        let far = parse("4000000000,7 -> 4000000004,7\n4000000002,5 -> 4000000002,9").unwrap();
+        assert_eq!(Ok(1), overlaps(&far, false, Backend::Auto, Raster::Strict));
+        let mut params = Params::new(DAY.params);
+        params.set("map", "dense").unwrap();
+        assert_eq!(
+            "the lines cover 4000000001x5 cells, too many for a dense map, try map=sparse",
+            solve("0,5 -> 4000000000,5\n0,9 -> 0,5", &params)
+                .unwrap_err()
+                .to_string()
+        );
+
+        // Other slopes need a raster that can draw them
+        let steep = "0,0 -> 2,6\n0,3 -> 2,3\n1,0 -> 1,6";
+        assert!(solve(steep, &Params::new(DAY.params)).is_err());
+        params.set("raster", "lattice").unwrap();
+        let answers = solve(steep, &params).unwrap();
+        assert_eq!(("1", "1"), (&*answers.part_1, &*answers.part_2));
+        params.set("raster", "bresenham").unwrap();
+        let answers = solve(steep, &params).unwrap();
+        assert_eq!(("1", "3"), (&*answers.part_1, &*answers.part_2));
+    }
+
+    fn print_map(map: &impl Map) {
+        for y in 0..=9 {
+            for x in 0..=9 {
+                match map.count(Point::new(x, y)) {
+                    0 => print!("."),
+                    n => print!("{}", n),
+                }
+            }
+            println!();
+        }
+    }
+}
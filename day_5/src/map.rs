@@ -0,0 +1,315 @@
+// Where the vents' lines overlap, counted either in a hash map of the cells covered or in a dense
+// grid covering the lines' bounds. The grid is far quicker when the lines fill much of their
+// bounds, which they usually do, and the hash map saves memory when they're spread thin.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::thread;
+
+use crate::raster::Raster;
+use crate::Point;
+
+/// Counts of how many lines cover each cell.
+pub trait Map: Send + Sized {
+    fn add(&mut self, p: Point);
+
+    /// Add the counts of another map covering the same area.
+    fn merge(&mut self, other: Self);
+
+    fn count(&self, p: Point) -> u32;
+
+    /// How many cells more than one line covers.
+    fn overlaps(&self) -> usize;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sparse {
+    counts: HashMap<Point, u32>,
+}
+
+impl Map for Sparse {
+    fn add(&mut self, p: Point) {
+        *self.counts.entry(p).or_insert(0) += 1;
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (p, count) in other.counts {
+            *self.counts.entry(p).or_insert(0) += count;
+        }
+    }
+
+    fn count(&self, p: Point) -> u32 {
+        self.counts.get(&p).copied().unwrap_or(0)
+    }
+
+    fn overlaps(&self) -> usize {
+        self.counts.values().filter(|&&n| n >= 2).count()
+    }
+}
+
+/// The smallest rectangle holding some points, inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// The bounds of the lines' ends, and so the lines, or `None` if there are no lines.
+    pub fn of(lines: &[(Point, Point)]) -> Option<Self> {
+        let mut points = lines.iter().flat_map(|&(p1, p2)| [p1, p2]);
+        let first = points.next()?;
+        Some(points.fold(
+            Bounds {
+                min: first,
+                max: first,
+            },
+            |b, p| Bounds {
+                min: Point::new(b.min.x.min(p.x), b.min.y.min(p.y)),
+                max: Point::new(b.max.x.max(p.x), b.max.y.max(p.y)),
+            },
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize + 1
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize + 1
+    }
+
+    pub fn area(&self) -> usize {
+        self.width().saturating_mul(self.height())
+    }
+
+    /// Whether a dense map of these bounds fits in `MAX_DENSE_BYTES`.
+    pub fn fits_dense(&self) -> bool {
+        self.area() <= MAX_DENSE_BYTES / 4
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+}
+
+/// Counts for every cell within some bounds, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dense {
+    pub bounds: Bounds,
+    counts: Vec<u32>,
+}
+
+impl Dense {
+    /// An empty map, which `bounds` have to fit, see `Bounds::fits_dense`.
+    pub fn new(bounds: Bounds) -> Self {
+        assert!(
+            bounds.fits_dense(),
+            "the bounds are too big for a dense map"
+        );
+        Self {
+            bounds,
+            counts: vec![0; bounds.area()],
+        }
+    }
+
+    /// The counts, row by row from the top left of the bounds.
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    pub fn into_counts(self) -> Vec<u32> {
+        self.counts
+    }
+
+    fn index(&self, p: Point) -> usize {
+        (p.y - self.bounds.min.y) as usize * self.bounds.width()
+            + (p.x - self.bounds.min.x) as usize
+    }
+}
+
+impl Map for Dense {
+    fn add(&mut self, p: Point) {
+        let i = self.index(p);
+        self.counts[i] += 1;
+    }
+
+    fn merge(&mut self, other: Self) {
+        assert_eq!(self.bounds, other.bounds, "the maps cover different areas");
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+    }
+
+    fn count(&self, p: Point) -> u32 {
+        if self.bounds.contains(p) {
+            self.counts[self.index(p)]
+        } else {
+            0
+        }
+    }
+
+    fn overlaps(&self) -> usize {
+        self.counts.iter().filter(|&&n| n >= 2).count()
+    }
+}
+
+/// Which map to count in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Pick the dense map when the lines cover enough of their bounds.
+    Auto,
+    Sparse,
+    Dense,
+}
+
+/// The dense map is picked when the lines' bounds have no more than this many cells for each
+/// cell the lines cover.
+const DENSE_CELLS_PER_COVERED: usize = 16;
+
+/// Dense maps never get bigger than this, counting every thread's.
+pub const MAX_DENSE_BYTES: usize = 1 << 30;
+
+/// Below this many covered cells rasterising isn't worth splitting between threads.
+const MIN_CELLS_PER_THREAD: usize = 1 << 16;
+
+impl Backend {
+    /// The backend to use for `lines`, working out which if it's `Auto`.
+    pub fn resolve(self, lines: &[(Point, Point)]) -> Backend {
+        if self != Backend::Auto {
+            return self;
+        }
+        let bounds = match Bounds::of(lines) {
+            Some(bounds) => bounds,
+            None => return Backend::Sparse,
+        };
+        let area = bounds.area();
+        if area <= covered(lines).saturating_mul(DENSE_CELLS_PER_COVERED) && bounds.fits_dense() {
+            Backend::Dense
+        } else {
+            Backend::Sparse
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "auto" => Ok(Backend::Auto),
+            "sparse" => Ok(Backend::Sparse),
+            "dense" => Ok(Backend::Dense),
+            _ => Err(format!(
+                "unknown map `{}`, expected auto, sparse or dense",
+                s
+            )),
+        }
+    }
+}
+
+/// Roughly how many cells the lines cover, counting overlaps more than once.
+pub fn covered(lines: &[(Point, Point)]) -> usize {
+    lines
+        .iter()
+        .map(|(p1, p2)| p1.x.abs_diff(p2.x).max(p1.y.abs_diff(p2.y)) as usize + 1)
+        .sum()
+}
+
+/// Count the cells `lines` cover, splitting them between as many threads as are worth it, each
+/// drawing into its own map from `new` before they're all merged.
+pub fn rasterise<M: Map>(
+    lines: &[(Point, Point)],
+    diagonals: bool,
+    raster: Raster,
+    max_threads: usize,
+    new: impl Fn() -> M + Sync,
+) -> M {
+    let threads = (covered(lines) / MIN_CELLS_PER_THREAD).clamp(1, max_threads.max(1));
+    let draw = |lines: &[(Point, Point)]| {
+        let mut map = new();
+        for &(p1, p2) in lines {
+            crate::line(&mut map, diagonals, raster, p1, p2);
+        }
+        map
+    };
+    if threads == 1 {
+        return draw(lines);
+    }
+
+    let chunk = lines.len().div_ceil(threads);
+    let mut maps = thread::scope(|s| {
+        let handles: Vec<_> = lines
+            .chunks(chunk)
+            .map(|lines| s.spawn(move || draw(lines)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Vec<_>>()
+    })
+    .into_iter();
+    let mut map = maps.next().unwrap();
+    for other in maps {
+        map.merge(other);
+    }
+    map
+}
+
+/// How many threads to rasterise a dense map for `bounds` with, so their maps all fit.
+pub fn dense_threads(bounds: Bounds) -> usize {
+    let available = thread::available_parallelism().map_or(1, |n| n.get());
+    available
+        .min(MAX_DENSE_BYTES / bounds.area().saturating_mul(4).max(1))
+        .max(1)
+}
+
+/// How many threads to rasterise a sparse map with.
+pub fn sparse_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        // Enough lines to be split between threads
+        let lines: Vec<_> = (0..2000u32)
+            .map(|i| {
+                let (a, b) = (i * 37 % 500, i * 91 % 500);
+                match i % 3 {
+                    0 => (Point::new(a, b), Point::new(a, 499 - b)),
+                    1 => (Point::new(a, b), Point::new(499 - a, b)),
+                    _ => (Point::new(a, a), Point::new(b, b)),
+                }
+            })
+            .collect();
+        let bounds = Bounds::of(&lines).unwrap();
+        assert_eq!((500, 500), (bounds.width(), bounds.height()));
+        assert_eq!(Backend::Dense, Backend::Auto.resolve(&lines));
+        assert_eq!(
+            Backend::Sparse,
+            Backend::Auto.resolve(&[
+                (Point::new(0, 0), Point::new(1, 1)),
+                (Point::new(1 << 20, 0), Point::new(1 << 20, 1))
+            ])
+        );
+
+        let raster = Raster::Strict;
+        let single = rasterise(&lines, true, raster, 1, Sparse::default);
+        let sparse = rasterise(&lines, true, raster, 4, Sparse::default);
+        let dense = rasterise(&lines, true, raster, 4, || Dense::new(bounds));
+        assert_eq!(single, sparse);
+        assert_eq!(single.overlaps(), dense.overlaps());
+        for p in [
+            Point::new(0, 0),
+            Point::new(250, 250),
+            Point::new(499, 3),
+            Point::new(600, 0),
+        ] {
+            assert_eq!(single.count(p), dense.count(p));
+        }
+    }
+}
@@ -0,0 +1,396 @@
+// Answering many questions about one vent map: how many lines cover a cell, the most any cell in a
+// rectangle is covered, which lines go through a cell, and which cells are covered at least k
+// times. The map is rasterised once into an index that answers each of them without going back
+// over the lines.
+//
+//     cargo run --release --bin day_5 -- --query
+//     echo "max 0,0 99,99" | cargo run --release --bin day_5 -- --query vents.txt --raster lattice
+//     cargo run --release --bin day_5 -- --query --straight < queries.txt
+
+use std::io::{self, BufRead, Write};
+
+use aoc::cli::Args;
+use aoc::parse::{self, Parser};
+
+use crate::map::{self, Bounds, Dense, MAX_DENSE_BYTES};
+use crate::raster::Raster;
+use crate::Point;
+
+/// Everything needed to answer queries about a map, built once from its lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    pub lines: Vec<(Point, Point)>,
+    pub bounds: Bounds,
+    /// The most any cell is covered in each block of 2^k by 2^k cells, for each level k, starting
+    /// with the counts themselves. The last level is a single block covering every cell.
+    levels: Vec<Level>,
+    /// Where each cell's lines start in `through`, with one more on the end for the last cell.
+    starts: Vec<usize>,
+    /// The indices of the lines through each cell, cell by cell.
+    through: Vec<u32>,
+    /// Every covered cell with its count, most covered first and row by row among equals.
+    by_count: Vec<(u32, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Level {
+    width: usize,
+    height: usize,
+    max: Vec<u32>,
+}
+
+// Roughly the most bytes an index of lines with `bounds`, covering `covered` cells counting
+// overlaps more than once, takes. Each cell has its count in every level, which come to at most
+// twice the counts themselves, and where its lines start. Each covered cell has a line index, and
+// at most one entry in `by_count`.
+fn index_bytes(bounds: Bounds, covered: usize) -> usize {
+    let cells = bounds.area().saturating_mul(2 * 4 + 8);
+    cells.saturating_add(covered.saturating_mul(4 + 8))
+}
+
+impl Index {
+    /// Index the cells `lines` cover, leaving out diagonal lines unless `diagonals`. The index
+    /// has to fit in `MAX_DENSE_BYTES`.
+    pub fn new(
+        lines: Vec<(Point, Point)>,
+        diagonals: bool,
+        raster: Raster,
+    ) -> Result<Self, String> {
+        raster.check(&lines)?;
+        let bounds = Bounds::of(&lines).ok_or("there are no lines to index")?;
+        // Cells are kept as `u32`s, which anything that fits does
+        if index_bytes(bounds, map::covered(&lines)) > MAX_DENSE_BYTES {
+            return Err(format!(
+                "the lines cover {}x{} cells, too many to index",
+                bounds.width(),
+                bounds.height()
+            ));
+        }
+
+        let threads = map::dense_threads(bounds);
+        let dense = map::rasterise(&lines, diagonals, raster, threads, || Dense::new(bounds));
+        let counts = dense.into_counts();
+        let cell = |p: Point| {
+            (p.y - bounds.min.y) as usize * bounds.width() + (p.x - bounds.min.x) as usize
+        };
+
+        // Each cell's lines are filled in backwards from where the next cell's start, so the
+        // lines go in last first and the starts end up where they should be
+        let mut starts = Vec::with_capacity(counts.len() + 1);
+        let mut end = 0;
+        for &count in &counts {
+            end += count as usize;
+            starts.push(end);
+        }
+        starts.push(end);
+        let mut through = vec![0; end];
+        for (i, &(p1, p2)) in lines.iter().enumerate().rev() {
+            crate::cells(diagonals, raster, p1, p2, |p| {
+                let c = cell(p);
+                starts[c] -= 1;
+                through[starts[c]] = i as u32;
+            });
+        }
+
+        let mut by_count: Vec<_> = (0..counts.len() as u32)
+            .filter(|&c| counts[c as usize] > 0)
+            .map(|c| (counts[c as usize], c))
+            .collect();
+        by_count.sort_unstable_by_key(|&(count, c)| (std::cmp::Reverse(count), c));
+
+        let mut levels = vec![Level {
+            width: bounds.width(),
+            height: bounds.height(),
+            max: counts,
+        }];
+        while let Some(below) = levels.last().filter(|l| l.width > 1 || l.height > 1) {
+            let (width, height) = (below.width.div_ceil(2), below.height.div_ceil(2));
+            let mut max = vec![0; width * height];
+            for y in 0..below.height {
+                for x in 0..below.width {
+                    let m = &mut max[y / 2 * width + x / 2];
+                    *m = (*m).max(below.max[y * below.width + x]);
+                }
+            }
+            levels.push(Level { width, height, max });
+        }
+
+        Ok(Self {
+            lines,
+            bounds,
+            levels,
+            starts,
+            through,
+            by_count,
+        })
+    }
+
+    fn cell(&self, p: Point) -> Option<usize> {
+        if self.bounds.contains(p) {
+            Some(
+                (p.y - self.bounds.min.y) as usize * self.bounds.width()
+                    + (p.x - self.bounds.min.x) as usize,
+            )
+        } else {
+            None
+        }
+    }
+
+    fn point(&self, cell: usize) -> Point {
+        let width = self.bounds.width();
+        Point::new(
+            self.bounds.min.x + (cell % width) as u32,
+            self.bounds.min.y + (cell / width) as u32,
+        )
+    }
+
+    /// How many lines cover `p`.
+    pub fn count(&self, p: Point) -> u32 {
+        self.cell(p).map_or(0, |c| self.levels[0].max[c])
+    }
+
+    /// The most lines covering any cell in the rectangle from `p1` to `p2`, inclusive, which can
+    /// be any two opposite corners.
+    pub fn max_in(&self, p1: Point, p2: Point) -> u32 {
+        let b = self.bounds;
+        let (x1, x2) = (p1.x.min(p2.x).max(b.min.x), p1.x.max(p2.x).min(b.max.x));
+        let (y1, y2) = (p1.y.min(p2.y).max(b.min.y), p1.y.max(p2.y).min(b.max.y));
+        if x1 > x2 || y1 > y2 {
+            return 0;
+        }
+        let xs = ((x1 - b.min.x) as usize, (x2 - b.min.x) as usize);
+        let ys = ((y1 - b.min.y) as usize, (y2 - b.min.y) as usize);
+        self.max_below(self.levels.len() - 1, 0, 0, xs, ys)
+    }
+
+    // The most in the rectangle `xs` by `ys` within block `(x, y)` of level `k`
+    fn max_below(
+        &self,
+        k: usize,
+        x: usize,
+        y: usize,
+        xs: (usize, usize),
+        ys: (usize, usize),
+    ) -> u32 {
+        let level = &self.levels[k];
+        if x >= level.width || y >= level.height {
+            return 0;
+        }
+        let (left, top) = (x << k, y << k);
+        let (right, bottom) = (left + (1 << k) - 1, top + (1 << k) - 1);
+        if right < xs.0 || left > xs.1 || bottom < ys.0 || top > ys.1 {
+            return 0;
+        }
+        if xs.0 <= left && right <= xs.1 && ys.0 <= top && bottom <= ys.1 {
+            return level.max[y * level.width + x];
+        }
+        let mut max = 0;
+        for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            max = max.max(self.max_below(k - 1, 2 * x + dx, 2 * y + dy, xs, ys));
+        }
+        max
+    }
+
+    /// The indices in `lines` of the lines through `p`, in order.
+    pub fn through(&self, p: Point) -> &[u32] {
+        match self.cell(p) {
+            Some(c) => &self.through[self.starts[c]..self.starts[c + 1]],
+            None => &[],
+        }
+    }
+
+    /// How many cells more than one line covers.
+    pub fn overlaps(&self) -> usize {
+        self.by_count.partition_point(|&(count, _)| count >= 2)
+    }
+
+    /// Every cell at least `k` lines cover with its count, most covered first.
+    pub fn at_least(&self, k: u32) -> impl Iterator<Item = (Point, u32)> + '_ {
+        let n = self
+            .by_count
+            .partition_point(|&(count, _)| count >= k.max(1));
+        self.by_count[..n]
+            .iter()
+            .map(move |&(count, c)| (self.point(c as usize), count))
+    }
+}
+
+/// A query about an index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query {
+    /// `count X,Y`
+    Count(Point),
+    /// `max X1,Y1 X2,Y2`
+    Max(Point, Point),
+    /// `through X,Y`
+    Through(Point),
+    /// `at-least K`
+    AtLeast(u32),
+}
+
+impl Query {
+    pub fn parse(s: &str) -> parse::Result<Self> {
+        fn point(p: &mut Parser) -> parse::Result<Point> {
+            p.skip_spaces();
+            let x = p.integer()?;
+            p.tag(",")?;
+            Ok(Point::new(x, p.integer()?))
+        }
+
+        Parser::parse(s, |p| {
+            p.skip_spaces();
+            match p.take_while(|c| c.is_alphabetic() || c == '-') {
+                "count" => Ok(Query::Count(point(p)?)),
+                "max" => Ok(Query::Max(point(p)?, point(p)?)),
+                "through" => Ok(Query::Through(point(p)?)),
+                "at-least" => {
+                    p.skip_spaces();
+                    Ok(Query::AtLeast(p.integer()?))
+                }
+                word => Err(p.error(format!(
+                    "unknown query `{}`, expected count, max, through or at-least",
+                    word
+                ))),
+            }
+        })
+    }
+
+    /// Write the answer to `output`, a line per line or cell for `through` and `at-least`.
+    pub fn answer(self, index: &Index, mut output: impl Write) -> io::Result<()> {
+        match self {
+            Query::Count(p) => writeln!(output, "{}", index.count(p)),
+            Query::Max(p1, p2) => writeln!(output, "{}", index.max_in(p1, p2)),
+            Query::Through(p) => {
+                for &i in index.through(p) {
+                    let (p1, p2) = index.lines[i as usize];
+                    writeln!(output, "{}: {},{} -> {},{}", i + 1, p1.x, p1.y, p2.x, p2.y)?;
+                }
+                Ok(())
+            }
+            Query::AtLeast(k) => {
+                for (p, count) in index.at_least(k) {
+                    writeln!(output, "{},{}: {}", p.x, p.y, count)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Answer the queries in `input`, one per line, writing each answer to `output` followed by a
+/// blank line. Queries that don't parse get an error in place of an answer.
+pub fn queries(index: &Index, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for (number, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Query::parse(&line) {
+            Ok(query) => query.answer(index, &mut output)?,
+            Err(mut e) => {
+                e.line = number + 1;
+                writeln!(output, "error: {}", e)?;
+            }
+        }
+        writeln!(output)?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// `day_5 --query`, reading queries from stdin.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut raster = Raster::Strict;
+    let mut diagonals = true;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--raster" | "-r" => raster = args.value(&arg)?.parse()?,
+            "--straight" => diagonals = false,
+            _ => args.path(arg)?,
+        }
+    }
+
+    let input = args.input(crate::DAY.input)?;
+    let lines = crate::parse(&input)?;
+    let index = Index::new(lines, diagonals, raster)?;
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    queries(&index, stdin.lock(), stdout.lock())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let example = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+        let lines = crate::parse(example).unwrap();
+        let index = Index::new(lines.clone(), true, Raster::Strict).unwrap();
+        assert_eq!(12, index.overlaps());
+        assert_eq!(
+            5,
+            Index::new(lines, false, Raster::Strict).unwrap().overlaps()
+        );
+
+        assert_eq!(3, index.count(Point::new(4, 4)));
+        assert_eq!(0, index.count(Point::new(100, 4)));
+        assert_eq!(&[1, 2, 8], index.through(Point::new(4, 4)));
+        assert_eq!(3, index.max_in(Point::new(9, 9), Point::new(0, 0)));
+        assert_eq!(3, index.max_in(Point::new(0, 0), Point::new(4, 8)));
+        assert_eq!(2, index.max_in(Point::new(0, 0), Point::new(3, 3)));
+        assert_eq!(1, index.max_in(Point::new(8, 0), Point::new(8, 1)));
+        assert_eq!(0, index.max_in(Point::new(20, 20), Point::new(30, 30)));
+        assert_eq!(
+            vec![(Point::new(4, 4), 3), (Point::new(6, 4), 3)],
+            index.at_least(3).collect::<Vec<_>>()
+        );
+        assert_eq!(12, index.at_least(2).count());
+
+        // Every rectangle against looking at every cell in it
+        for (x1, y1) in (0..10).flat_map(|x| (0..10).map(move |y| (x, y))) {
+            for (x2, y2) in (x1..10).flat_map(|x| (y1..10).map(move |y| (x, y))) {
+                let max = (x1..=x2)
+                    .flat_map(|x| (y1..=y2).map(move |y| Point::new(x, y)))
+                    .map(|p| index.count(p))
+                    .max();
+                assert_eq!(
+                    max,
+                    Some(index.max_in(Point::new(x1, y1), Point::new(x2, y2)))
+                );
+            }
+        }
+
+        let mut output = Vec::new();
+        let input = "count 7,4\nthrough 2,2\n\nmax 0,0 3,3\nat-least 3\ncount 1\n";
+        queries(&index, input.as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            "2\n\n4: 2,2 -> 2,1\n9: 0,0 -> 8,8\n\n2\n\n4,4: 3\n6,4: 3\n\n\
+             error: line 6, column 8: expected `,`, found end of line\n\n",
+            String::from_utf8(output).unwrap()
+        );
+
+        assert!(Index::new(Vec::new(), true, Raster::Strict).is_err());
+
+        // Small enough for a dense map, but not with everything else the index keeps
+        let lines = crate::parse("0,0 -> 20000,0\n0,0 -> 0,10000").unwrap();
+        assert!(Bounds::of(&lines).unwrap().fits_dense());
+        assert_eq!(
+            Err("the lines cover 20001x10001 cells, too many to index".to_string()),
+            Index::new(lines, true, Raster::Strict).map(|i| i.overlaps())
+        );
+    }
+}
@@ -0,0 +1,215 @@
+// Which cells a line covers. The puzzle only has horizontal, vertical and 45° lines, which cover
+// the same cells whichever way they're drawn, apart from supercover including the cells they pass
+// the corners of. Lines at other slopes need a policy to say which cells they cover.
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Raster {
+    /// Only horizontal, vertical and 45° lines, as in the puzzle.
+    Strict,
+    /// One cell per step along the longer axis, the closest to the line.
+    Bresenham,
+    /// Every cell the line passes through, including both sides of a corner it passes exactly
+    /// through.
+    Supercover,
+    /// Only the cells the line passes exactly through the middle of.
+    Lattice,
+}
+
+impl Raster {
+    pub fn supports(self, p1: Point, p2: Point) -> bool {
+        let (dx, dy) = (p1.x.abs_diff(p2.x), p1.y.abs_diff(p2.y));
+        self != Raster::Strict || dx == 0 || dy == 0 || dx == dy
+    }
+
+    /// An error for the first of `lines` this can't draw.
+    pub fn check(self, lines: &[(Point, Point)]) -> Result<(), String> {
+        match lines.iter().find(|&&(p1, p2)| !self.supports(p1, p2)) {
+            Some((p1, p2)) => Err(format!(
+                "the line {},{} -> {},{} isn't horizontal, vertical or 45°, which is all the \
+                 strict raster draws",
+                p1.x, p1.y, p2.x, p2.y
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Call `f` with each cell from `p1` to `p2`, which must be supported.
+    pub fn cells(self, p1: Point, p2: Point, mut f: impl FnMut(Point)) {
+        assert!(self.supports(p1, p2), "the line isn't supported");
+        let (x1, y1, x2, y2) = (p1.x as i64, p1.y as i64, p2.x as i64, p2.y as i64);
+        let (dx, dy) = ((x2 - x1).abs(), (y2 - y1).abs());
+        let (sx, sy) = ((x2 - x1).signum(), (y2 - y1).signum());
+        let mut visit = |x: i64, y: i64| f(Point::new(x as u32, y as u32));
+
+        match self {
+            Raster::Strict | Raster::Lattice => {
+                let steps = gcd(dx, dy);
+                let (step_x, step_y) = match steps {
+                    0 => (0, 0),
+                    _ => ((x2 - x1) / steps, (y2 - y1) / steps),
+                };
+                for i in 0..=steps {
+                    visit(x1 + i * step_x, y1 + i * step_y);
+                }
+            }
+            Raster::Bresenham => {
+                let (mut x, mut y) = (x1, y1);
+                let mut error = dx - dy;
+                loop {
+                    visit(x, y);
+                    if (x, y) == (x2, y2) {
+                        break;
+                    }
+                    let twice = 2 * error;
+                    if twice > -dy {
+                        error -= dy;
+                        x += sx;
+                    }
+                    if twice < dx {
+                        error += dx;
+                        y += sy;
+                    }
+                }
+            }
+            Raster::Supercover => {
+                // Stepping to whichever of the next column or row boundary the line reaches
+                // first
+                let (mut x, mut y) = (x1, y1);
+                let (mut i, mut j) = (0, 0);
+                visit(x, y);
+                while i < dx || j < dy {
+                    match next_boundary(i, j, dx, dy) {
+                        Ordering::Equal => {
+                            visit(x + sx, y);
+                            visit(x, y + sy);
+                            x += sx;
+                            y += sy;
+                            i += 1;
+                            j += 1;
+                        }
+                        Ordering::Less => {
+                            x += sx;
+                            i += 1;
+                        }
+                        Ordering::Greater => {
+                            y += sy;
+                            j += 1;
+                        }
+                    }
+                    visit(x, y);
+                }
+            }
+        }
+    }
+}
+
+// Whether a line `dx` by `dy` reaches its next column boundary (`Less`) or row boundary
+// (`Greater`) first after `i` columns and `j` rows, comparing (0.5 + i) / dx with (0.5 + j) / dy
+// in whole numbers. The products can be too big for an `i64`.
+fn next_boundary(i: i64, j: i64, dx: i64, dy: i64) -> Ordering {
+    let column = i128::from(1 + 2 * i) * i128::from(dy);
+    let row = i128::from(1 + 2 * j) * i128::from(dx);
+    column.cmp(&row)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl FromStr for Raster {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "strict" => Ok(Raster::Strict),
+            "bresenham" => Ok(Raster::Bresenham),
+            "supercover" => Ok(Raster::Supercover),
+            "lattice" => Ok(Raster::Lattice),
+            _ => Err(format!(
+                "unknown raster `{}`, expected strict, bresenham, supercover or lattice",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(raster: Raster, (x1, y1): (u32, u32), (x2, y2): (u32, u32)) -> Vec<(u32, u32)> {
+        let mut cells = Vec::new();
+        raster.cells(Point::new(x1, y1), Point::new(x2, y2), |p| {
+            cells.push((p.x, p.y))
+        });
+        cells
+    }
+
+    #[test]
+    fn it_works() {
+        // The puzzle's lines come out the same, bar supercover's corners
+        for raster in [Raster::Strict, Raster::Bresenham, Raster::Lattice] {
+            assert_eq!(vec![(3, 1), (2, 2), (1, 3)], cells(raster, (3, 1), (1, 3)));
+            assert_eq!(vec![(2, 5), (2, 4)], cells(raster, (2, 5), (2, 4)));
+            assert_eq!(vec![(7, 7)], cells(raster, (7, 7), (7, 7)));
+        }
+        assert_eq!(
+            vec![(0, 0), (1, 0), (0, 1), (1, 1)],
+            cells(Raster::Supercover, (0, 0), (1, 1))
+        );
+
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2)],
+            cells(Raster::Bresenham, (0, 0), (4, 2))
+        );
+        assert_eq!(
+            vec![(4, 2), (3, 2), (2, 1), (1, 1), (0, 0)],
+            cells(Raster::Bresenham, (4, 2), (0, 0))
+        );
+        assert_eq!(
+            vec![(0, 0), (2, 1), (4, 2)],
+            cells(Raster::Lattice, (0, 0), (4, 2))
+        );
+        assert_eq!(vec![(0, 0), (3, 1)], cells(Raster::Lattice, (0, 0), (3, 1)));
+        assert_eq!(
+            vec![(0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (3, 1)],
+            cells(Raster::Supercover, (0, 0), (3, 1))
+        );
+        assert_eq!(
+            vec![(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2)],
+            cells(Raster::Supercover, (0, 0), (2, 2))
+        );
+
+        // Far past where the products fit in an `i64`
+        let big = 4_000_000_000;
+        assert_eq!(Ordering::Equal, next_boundary(big - 1, big - 1, big, big));
+        assert_eq!(Ordering::Less, next_boundary(big - 2, big - 1, big, big));
+        assert_eq!(
+            Ordering::Greater,
+            next_boundary(big - 1, big - 1, big - 1, big)
+        );
+
+        let lines = [
+            (Point::new(0, 0), Point::new(4, 4)),
+            (Point::new(0, 0), Point::new(4, 2)),
+        ];
+        assert!(Raster::Lattice.check(&lines).is_ok());
+        assert_eq!(
+            Err(
+                "the line 0,0 -> 4,2 isn't horizontal, vertical or 45°, which is all the strict \
+                 raster draws"
+                    .to_string()
+            ),
+            Raster::Strict.check(&lines)
+        );
+    }
+}
@@ -0,0 +1,320 @@
+// Counting overlaps without visiting every cell the lines cover, for maps too big to rasterise.
+//
+// Lines of the same orientation only overlap if they're on the same line, where a sweep along it
+// finds the stretches covered twice. Lines of different orientations only overlap where they
+// cross, and turning each pair of orientations into horizontal and vertical lines lets a sweep
+// across them find every crossing. The overlapping cells are those stretches and crossings, less
+// the crossings already counted in a stretch or at another crossing.
+
+use std::collections::BTreeMap;
+
+use crate::Point;
+
+// Which way a line goes, as the step between its cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+const FAMILIES: [Family; 4] = [
+    Family::Horizontal,
+    Family::Vertical,
+    Family::Diagonal,
+    Family::AntiDiagonal,
+];
+
+impl Family {
+    fn of(p1: Point, p2: Point) -> Option<Family> {
+        let (dx, dy) = (p2.x as i64 - p1.x as i64, p2.y as i64 - p1.y as i64);
+        match (dx, dy) {
+            (_, 0) => Some(Family::Horizontal),
+            (0, _) => Some(Family::Vertical),
+            _ if dx == dy => Some(Family::Diagonal),
+            _ if dx == -dy => Some(Family::AntiDiagonal),
+            _ => None,
+        }
+    }
+
+    fn step(self) -> (i64, i64) {
+        match self {
+            Family::Horizontal => (1, 0),
+            Family::Vertical => (0, 1),
+            Family::Diagonal => (1, 1),
+            Family::AntiDiagonal => (1, -1),
+        }
+    }
+
+    // Which of the family's lines a cell is on, and how far along it
+    fn key(self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Family::Horizontal => (y, x),
+            Family::Vertical => (x, y),
+            Family::Diagonal => (x - y, x),
+            Family::AntiDiagonal => (x + y, x),
+        }
+    }
+
+    fn cell(self, key: i64, along: i64) -> (i64, i64) {
+        match self {
+            Family::Horizontal => (along, key),
+            Family::Vertical => (key, along),
+            Family::Diagonal => (along, along - key),
+            Family::AntiDiagonal => (along, key - along),
+        }
+    }
+}
+
+// A stretch of one of a family's lines, inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Stretch {
+    key: i64,
+    start: i64,
+    end: i64,
+}
+
+// The stretches a family's lines cover, and the stretches they cover more than once
+#[derive(Debug, Default)]
+struct Coverage {
+    covered: Vec<Stretch>,
+    overlaps: Vec<Stretch>,
+}
+
+impl Coverage {
+    fn new(mut stretches: Vec<Stretch>) -> Self {
+        // Starts and the cells after ends, sweeping along each line in turn
+        let mut events: Vec<(i64, i64, i32)> = Vec::with_capacity(stretches.len() * 2);
+        for s in stretches.drain(..) {
+            events.push((s.key, s.start, 1));
+            events.push((s.key, s.end + 1, -1));
+        }
+        events.sort_unstable();
+
+        let mut coverage = Coverage::default();
+        let mut i = 0;
+        while i < events.len() {
+            let (key, mut depth) = (events[i].0, 0);
+            let (mut covered_from, mut overlap_from) = (0, 0);
+            while i < events.len() && events[i].0 == key {
+                let along = events[i].1;
+                let before = depth;
+                while i < events.len() && events[i].0 == key && events[i].1 == along {
+                    depth += events[i].2;
+                    i += 1;
+                }
+                match (before >= 1, depth >= 1) {
+                    (false, true) => covered_from = along,
+                    (true, false) => coverage.covered.push(Stretch {
+                        key,
+                        start: covered_from,
+                        end: along - 1,
+                    }),
+                    _ => (),
+                }
+                match (before >= 2, depth >= 2) {
+                    (false, true) => overlap_from = along,
+                    (true, false) => coverage.overlaps.push(Stretch {
+                        key,
+                        start: overlap_from,
+                        end: along - 1,
+                    }),
+                    _ => (),
+                }
+            }
+        }
+        coverage
+    }
+
+    fn overlaps_at(&self, key: i64, along: i64) -> bool {
+        let i = self
+            .overlaps
+            .partition_point(|s| (s.key, s.end) < (key, along));
+        self.overlaps
+            .get(i)
+            .is_some_and(|s| s.key == key && s.start <= along)
+    }
+}
+
+/// How many cells more than one of `lines` covers, leaving out diagonal lines unless
+/// `diagonals`. Only horizontal, vertical and 45° lines can be counted.
+pub fn overlaps(lines: &[(Point, Point)], diagonals: bool) -> Result<usize, String> {
+    let mut stretches: [Vec<Stretch>; 4] = Default::default();
+    for &(p1, p2) in lines {
+        let family = Family::of(p1, p2).ok_or_else(|| {
+            format!(
+                "can't sweep the line {},{} -> {},{}, which isn't horizontal, vertical or 45°",
+                p1.x, p1.y, p2.x, p2.y
+            )
+        })?;
+        if !diagonals && matches!(family, Family::Diagonal | Family::AntiDiagonal) {
+            continue;
+        }
+        let (key, a) = family.key((p1.x as i64, p1.y as i64));
+        let (_, b) = family.key((p2.x as i64, p2.y as i64));
+        stretches[family as usize].push(Stretch {
+            key,
+            start: a.min(b),
+            end: a.max(b),
+        });
+    }
+    let coverage: Vec<Coverage> = stretches
+        .iter_mut()
+        .map(|s| Coverage::new(s.split_off(0)))
+        .collect();
+
+    let mut crossings = Vec::new();
+    for (i, &f) in FAMILIES.iter().enumerate() {
+        for &g in &FAMILIES[i + 1..] {
+            crossings.extend(crossing(
+                f,
+                &coverage[f as usize].covered,
+                g,
+                &coverage[g as usize].covered,
+            ));
+        }
+    }
+    crossings.sort_unstable();
+    crossings.dedup();
+
+    // Each crossing is a cell covered twice, unless it's already counted as part of one or more
+    // of the stretches
+    let mut count: usize = coverage
+        .iter()
+        .flat_map(|c| &c.overlaps)
+        .map(|s| (s.end - s.start + 1) as usize)
+        .sum();
+    for &cell in &crossings {
+        let counted = FAMILIES
+            .iter()
+            .filter(|&&f| {
+                let (key, along) = f.key(cell);
+                coverage[f as usize].overlaps_at(key, along)
+            })
+            .count();
+        if counted == 0 {
+            count += 1;
+        } else {
+            count -= counted - 1;
+        }
+    }
+    Ok(count)
+}
+
+// Every cell where one of `f`'s stretches crosses one of `g`'s.
+//
+// In coordinates `(u, v)` where `v` is constant along `f`'s lines and `u` along `g`'s, `f`'s
+// stretches are horizontal and `g`'s vertical, so a sweep across `u` finds where they cross. Not
+// every crossing is a cell, as diagonals can cross between cells.
+fn crossing(f: Family, fs: &[Stretch], g: Family, gs: &[Stretch]) -> Vec<(i64, i64)> {
+    let cross = |(dx, dy): (i64, i64), (x, y): (i64, i64)| dx * y - dy * x;
+    let (df, dg) = (f.step(), g.step());
+    let uv = |(x, y)| (cross(dg, (x, y)), cross(df, (x, y)));
+
+    // Starts of `f`'s stretches, then `g`'s stretches, then ends of `f`'s, at each `u`
+    const START: u8 = 0;
+    const CROSS: u8 = 1;
+    const END: u8 = 2;
+    let mut events = Vec::with_capacity(fs.len() * 2 + gs.len());
+    for s in fs {
+        let (u1, v) = uv(f.cell(s.key, s.start));
+        let (u2, _) = uv(f.cell(s.key, s.end));
+        events.push((u1.min(u2), START, v, v));
+        events.push((u1.max(u2), END, v, v));
+    }
+    for s in gs {
+        let (u, v1) = uv(g.cell(s.key, s.start));
+        let (_, v2) = uv(g.cell(s.key, s.end));
+        events.push((u, CROSS, v1.min(v2), v1.max(v2)));
+    }
+    events.sort_unstable();
+
+    let det = cross(df, dg);
+    let mut active: BTreeMap<i64, u32> = BTreeMap::new();
+    let mut cells = Vec::new();
+    for (u, kind, v1, v2) in events {
+        match kind {
+            START => *active.entry(v1).or_insert(0) += 1,
+            END => {
+                let n = active.get_mut(&v1).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    active.remove(&v1);
+                }
+            }
+            _ => {
+                for &v in active.range(v1..=v2).map(|(v, _)| v) {
+                    let (x, y) = (dg.0 * v - df.0 * u, dg.1 * v - df.1 * u);
+                    if x % det == 0 && y % det == 0 {
+                        cells.push((x / det, y / det));
+                    }
+                }
+            }
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::{self, Backend};
+    use crate::raster::Raster;
+
+    #[test]
+    fn it_works() {
+        // Lines in a small area, so they overlap a lot, checked against rasterising them
+        let mut seed = 5u64;
+        let mut random = |n: u32| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as u32 % n
+        };
+        for _ in 0..50 {
+            let lines: Vec<_> = (0..40)
+                .map(|_| {
+                    let (x, y, d) = (random(30), random(30), random(20));
+                    let p1 = Point::new(x + 20, y + 20);
+                    let p2 = match random(4) {
+                        0 => Point::new(x + d, y + 20),
+                        1 => Point::new(x + 20, y + d),
+                        2 => Point::new(x + 20 + d, y + 20 + d),
+                        _ => Point::new(x + 20 + d, y + 20 - d),
+                    };
+                    (p1, p2)
+                })
+                .collect();
+            for diagonals in [false, true] {
+                assert_eq!(
+                    crate::overlaps(&lines, diagonals, Backend::Dense, Raster::Strict),
+                    overlaps(&lines, diagonals)
+                );
+            }
+        }
+        assert_eq!(0, map::covered(&[]));
+
+        // Far too big to rasterise
+        let big = 4_000_000_000;
+        let lines = [
+            (Point::new(0, 0), Point::new(big, big)),
+            (Point::new(0, big), Point::new(big, 0)),
+            (Point::new(0, 1), Point::new(big, 1)),
+            (Point::new(1, 0), Point::new(1, big)),
+            (Point::new(big, 3), Point::new(0, 3)),
+            (Point::new(5, 1), Point::new(10, 1)),
+            (Point::new(1, 5), Point::new(1, big)),
+        ];
+        // Along y = 1 and x = 1, and six crossings that aren't on either
+        assert_eq!(Ok(6 + (big as usize - 4) + 6), overlaps(&lines, true));
+
+        assert_eq!(
+            Err(
+                "can't sweep the line 0,0 -> 2,1, which isn't horizontal, vertical or 45°"
+                    .to_string()
+            ),
+            overlaps(&[(Point::new(0, 0), Point::new(2, 1))], true)
+        );
+    }
+}
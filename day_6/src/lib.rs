@@ -0,0 +1,156 @@
+use aoc::params::{Param, Params};
+use aoc::parse::Parser;
+use aoc::{Alternative, Answers, Day};
+
+pub mod life;
+pub mod matrix;
+
+use matrix::{Exact, Modulo};
+
+pub const DAY: Day = Day {
+    name: "day_6",
+    input: include_str!("../input"),
+    params: &[
+        Param::new("part_1_days", "80", "Days to simulate for part 1"),
+        Param::new("part_2_days", "256", "Days to simulate for part 2"),
+        Param::new(
+            "modulus",
+            "none",
+            "Count the fish modulo this, for the matrix alternative, or none to count exactly",
+        ),
+    ],
+    answers: ["350605", "1592778185024"],
+    solve,
+    alternatives: &[Alternative {
+        name: "matrix",
+        solve: solve_matrix,
+    }],
+};
+
+/// Exact counts take too long beyond this many days, as they get so big.
+const MAX_EXACT_DAYS: u64 = 1_000_000;
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let school = parse(input)?;
+
+    let part_1 = count(&school, params.get("part_1_days")?)?;
+    let part_2 = count(&school, params.get("part_2_days")?)?;
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+// Simulating a day at a time while the fish fit in a `usize`, and counting them exactly with the
+// matrix when they don't
+fn count(school: &[usize], days: usize) -> aoc::Result<String> {
+    match simulate_n(school.to_vec(), days).and_then(|school| sum(&school)) {
+        Some(n) => Ok(n.to_string()),
+        None => exact(school, days as u64),
+    }
+}
+
+fn exact(school: &[usize], days: u64) -> aoc::Result<String> {
+    if days > MAX_EXACT_DAYS {
+        return Err(format!(
+            "{} days is too many to count exactly, run day_6/matrix with a modulus to count \
+             modulo it",
+            days
+        )
+        .into());
+    }
+    Ok(matrix::population(&Exact, school, days).to_string())
+}
+
+// Raising the matrix of a day to the power of the days, see `matrix`
+fn solve_matrix(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let school = parse(input)?;
+    let days: [u64; 2] = [params.get("part_1_days")?, params.get("part_2_days")?];
+
+    let [part_1, part_2] = match params.value("modulus")? {
+        "none" => [exact(&school, days[0])?, exact(&school, days[1])?],
+        modulus => {
+            let modulus = match modulus.parse() {
+                Ok(m) if m >= 2 => Modulo(m),
+                _ => {
+                    return Err(
+                        format!("the modulus `{}` isn't a number of at least 2", modulus).into(),
+                    )
+                }
+            };
+            days.map(|d| matrix::population(&modulus, &school, d).to_string())
+        }
+    };
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+/// The number of fish with each timer, from 0 to 8.
+fn parse(input: &str) -> aoc::Result<Vec<usize>> {
+    let input = Parser::parse(input, |p| {
+        p.list(",", |n| {
+            let start = *n;
+            match n.integer::<usize>()? {
+                timer if timer > 8 => {
+                    Err(start.error(format!("timers only go up to 8, found {}", timer)))
+                }
+                timer => Ok(timer),
+            }
+        })
+    })?;
+
+    let mut school = vec![0; 9];
+    for &fish in &input {
+        school[fish] += 1;
+    }
+    assert_eq!(input.len(), school.iter().sum());
+    Ok(school)
+}
+
+// Each simulation, a 0 becomes a 6 and adds a new 8 to the end of the list, while each other
+// number decreases by 1 if it was present at the start of the day. `None` if there are too many
+// fish to count.
+fn simulate(mut school: Vec<usize>) -> Option<Vec<usize>> {
+    let count = school.remove(0); // Remove the fish with 0 days left
+    school.push(count); // Spawn new fish at day 8
+    school[6] = school[6].checked_add(count)?; // Add old "zero" fish to the 6 day fish
+    Some(school)
+}
+
+fn simulate_n(school: Vec<usize>, n: usize) -> Option<Vec<usize>> {
+    (0..n).try_fold(school, |school, _| simulate(school))
+}
+
+fn sum(input: &[usize]) -> Option<usize> {
+    input.iter().try_fold(0usize, |sum, &n| sum.checked_add(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let params = Params::new(DAY.params);
+        let answers = solve("3,4,3,1,2", &params).unwrap();
+        assert_eq!(
+            ("5934", "26984457539"),
+            (&*answers.part_1, &*answers.part_2)
+        );
+
+        assert_eq!(
+            "line 1, column 5: timers only go up to 8, found 9",
+            solve("3,4,9", &params).unwrap_err().to_string()
+        );
+
+        // Past `usize`, counted exactly
+        let mut params = Params::new(DAY.params);
+        params.set("part_2_days", "1000").unwrap();
+        let answers = solve("3,4,3,1,2", &params).unwrap();
+        assert_eq!(
+            matrix::population(&Exact, &[0, 1, 1, 2, 1, 0, 0, 0, 0], 1000).to_string(),
+            answers.part_2
+        );
+        assert_eq!(None, simulate_n(vec![0, 1, 1, 2, 1, 0, 0, 0, 0], 1000));
+        params.set("part_2_days", "2000000").unwrap();
+        assert!(solve("3,4,3,1,2", &params).is_err());
+    }
+}
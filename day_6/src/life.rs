@@ -0,0 +1,373 @@
+// Schools of several species living by their own rules: the timer a fish resets to after giving
+// birth, the timer its young start on, how many young it has at once, and optionally the age it
+// dies at. The puzzle's lanternfish reset to 6, start at 8, have one at a time and never die.
+//
+// Each line of the input is a species' fish timers, after its name and any rules that aren't the
+// lanternfish's. A line of timers alone is lanternfish.
+//
+//     guppy reset=3 newborn=5 litter=2 max-age=40: 1,2,3,1
+//     lanternfish: 3,4,3,1,2
+//
+// Fish in the input are newborn, so their age counts from the first day.
+//
+//     cargo run --bin day_6 -- --life
+//     cargo run --bin day_6 -- --life pond.txt --days 200 --csv > pond.csv
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use aoc::cli::Args;
+use aoc::parse::{self, Parser};
+
+/// The highest timer a fish can have.
+pub const MAX_TIMER: usize = 1_000;
+
+/// The most ages and timers a species can be counted by, so a typo can't eat all the memory.
+pub const MAX_COUNTS: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// The timer a fish goes back to after giving birth.
+    pub reset: usize,
+    /// The timer newborns start on.
+    pub newborn: usize,
+    /// How many young each fish has at once.
+    pub litter: u128,
+    /// The age fish die at, if they die.
+    pub max_age: Option<usize>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            reset: 6,
+            newborn: 8,
+            litter: 1,
+            max_age: None,
+        }
+    }
+}
+
+/// How many fish of a species have each timer.
+pub type Histogram = Vec<u128>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Species {
+    pub name: String,
+    pub rules: Rules,
+    /// One more than the highest timer.
+    timers: usize,
+    /// How many fish have each age and timer, age by age. Without a maximum age every fish is
+    /// counted as age 0.
+    counts: Vec<u128>,
+}
+
+impl Species {
+    /// An error if there'd be more than `MAX_COUNTS` ages and timers to count.
+    pub fn new(name: &str, rules: Rules, fish: &[usize]) -> Result<Self, String> {
+        let timers = fish
+            .iter()
+            .copied()
+            .chain([rules.reset, rules.newborn])
+            .max()
+            .unwrap()
+            .saturating_add(1);
+        let ages = rules.max_age.unwrap_or(1);
+        let len = timers
+            .checked_mul(ages)
+            .filter(|&len| len <= MAX_COUNTS)
+            .ok_or_else(|| {
+                format!(
+                    "{} timers for {} ages is more than the {} a species can have",
+                    timers, ages, MAX_COUNTS
+                )
+            })?;
+        let mut counts = vec![0; len];
+        for &timer in fish {
+            counts[timer] += 1;
+        }
+        Ok(Self {
+            name: name.to_string(),
+            rules,
+            timers,
+            counts,
+        })
+    }
+
+    /// One day: fish at 0 give birth and reset, every other timer counts down, and fish reaching
+    /// the maximum age die. A fish still gives birth on the day it dies.
+    pub fn step(&mut self) -> Result<(), String> {
+        let Rules {
+            reset,
+            newborn,
+            litter,
+            max_age,
+        } = self.rules;
+        let overflow = || format!("there are too many {} to count", self.name);
+        let t = self.timers;
+        let mut next = vec![0u128; self.counts.len()];
+        for (i, &count) in self.counts.iter().enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let (age, timer) = (i / t, i % t);
+            let (dies, older) = match max_age {
+                Some(max) => (age + 1 >= max, age + 1),
+                None => (false, 0),
+            };
+            if timer == 0 {
+                let young = count.checked_mul(litter).ok_or_else(overflow)?;
+                next[newborn] = next[newborn].checked_add(young).ok_or_else(overflow)?;
+            }
+            if !dies {
+                let timer = if timer == 0 { reset } else { timer - 1 };
+                let n = &mut next[older * t + timer];
+                *n = n.checked_add(count).ok_or_else(overflow)?;
+            }
+        }
+        self.counts = next;
+        Ok(())
+    }
+
+    pub fn histogram(&self) -> Histogram {
+        let mut histogram = vec![0; self.timers];
+        for (i, &count) in self.counts.iter().enumerate() {
+            histogram[i % self.timers] += count;
+        }
+        histogram
+    }
+}
+
+/// Every species' histogram on every day, so `days[d][s]` is species `s` after `d` days.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    pub names: Vec<String>,
+    pub days: Vec<Vec<Histogram>>,
+}
+
+impl History {
+    pub fn record(mut species: Vec<Species>, days: usize) -> Result<Self, String> {
+        let census = |species: &[Species]| species.iter().map(Species::histogram).collect();
+        let mut history = Self {
+            names: species.iter().map(|s| s.name.clone()).collect(),
+            days: vec![census(&species)],
+        };
+        for _ in 0..days {
+            for s in &mut species {
+                s.step()?;
+            }
+            history.days.push(census(&species));
+        }
+        Ok(history)
+    }
+
+    /// How many fish of each species there are after the last day.
+    pub fn totals(&self) -> Vec<u128> {
+        let last = self.days.last().unwrap();
+        last.iter().map(|h| h.iter().sum()).collect()
+    }
+
+    /// A row per day and species, with a column for every timer any species uses.
+    pub fn to_csv(&self) -> String {
+        let timers = self.days[0].iter().map(Vec::len).max().unwrap_or(0);
+        let mut csv = "day,species".to_string();
+        for timer in 0..timers {
+            write!(csv, ",timer_{}", timer).unwrap();
+        }
+        csv.push_str(",total\n");
+        for (day, histograms) in self.days.iter().enumerate() {
+            for (name, histogram) in self.names.iter().zip(histograms) {
+                write!(csv, "{},{}", day, name).unwrap();
+                for timer in 0..timers {
+                    write!(csv, ",{}", histogram.get(timer).unwrap_or(&0)).unwrap();
+                }
+                writeln!(csv, ",{}", histogram.iter().sum::<u128>()).unwrap();
+            }
+        }
+        csv
+    }
+}
+
+/// The species in `input`, a line each, see the top of this module.
+pub fn parse(input: &str) -> parse::Result<Vec<Species>> {
+    let mut names = HashSet::new();
+    Parser::parse(input, |p| {
+        p.lines(|line| {
+            let start = *line;
+            let named = !line.peek().is_some_and(|c| c.is_ascii_digit());
+            let name = match named {
+                true => line.take_while(|c| c.is_alphanumeric() || c == '-' || c == '_'),
+                false => "lanternfish",
+            };
+            if name.is_empty() {
+                return Err(line.error("expected a species name"));
+            }
+            if !names.insert(name) {
+                return Err(line.error(format!("{} is already a species", name)));
+            }
+
+            let rules = match named {
+                true => rules(line)?,
+                false => Rules::default(),
+            };
+            let fish = line.list(",", timer)?;
+            Species::new(name, rules, &fish).map_err(|e| start.error(e))
+        })
+    })
+}
+
+// The rules after a species' name, up to the `:` before its fish
+fn rules(line: &mut Parser) -> parse::Result<Rules> {
+    let mut rules = Rules::default();
+    loop {
+        line.skip_spaces();
+        if line.eat(":") {
+            line.skip_spaces();
+            return Ok(rules);
+        }
+        let rest = line.remaining();
+        let end = rest.find(|c: char| c == '=' || c == ':' || c.is_whitespace());
+        let key = &rest[..end.unwrap_or(rest.len())];
+        if !["reset", "newborn", "litter", "max-age"].contains(&key) {
+            return Err(line.error(format!(
+                "unknown rule `{}`, expected reset, newborn, litter or max-age",
+                key
+            )));
+        }
+        line.tag(key)?;
+        line.tag("=")?;
+        match key {
+            "reset" => rules.reset = timer(line)?,
+            "newborn" => rules.newborn = timer(line)?,
+            "litter" => rules.litter = line.integer()?,
+            _ => {
+                let start = *line;
+                match line.integer()? {
+                    0 => return Err(start.error("max-age needs to be at least 1")),
+                    age if age > MAX_COUNTS => {
+                        return Err(start.error(format!("max-age can be at most {}", MAX_COUNTS)))
+                    }
+                    age => rules.max_age = Some(age),
+                }
+            }
+        }
+    }
+}
+
+// A timer of at most `MAX_TIMER`
+fn timer(p: &mut Parser) -> parse::Result<usize> {
+    let start = *p;
+    match p.integer()? {
+        timer if timer > MAX_TIMER => Err(start.error(format!(
+            "timers only go up to {}, found {}",
+            MAX_TIMER, timer
+        ))),
+        timer => Ok(timer),
+    }
+}
+
+/// `day_6 --life`, simulating the bundled input's lanternfish unless given a file.
+pub fn main(args: impl Iterator<Item = String>) -> aoc::Result<()> {
+    let mut days = 80;
+    let mut csv = false;
+
+    let mut args = Args::new(args);
+    while let Some(arg) = args.next_arg() {
+        match arg.as_str() {
+            "--days" | "-d" => days = args.parse(&arg, "a number of days")?,
+            "--csv" => csv = true,
+            _ => args.path(arg)?,
+        }
+    }
+
+    let input = args.input(crate::DAY.input)?;
+    let species = parse(&input)?;
+    let history = History::record(species, days)?;
+
+    if csv {
+        print!("{}", history.to_csv());
+        return Ok(());
+    }
+    for (day, histograms) in history.days.iter().enumerate() {
+        for (name, histogram) in history.names.iter().zip(histograms) {
+            let counts: Vec<_> = histogram.iter().map(u128::to_string).collect();
+            println!(
+                "day {} {}: {} ({})",
+                day,
+                name,
+                counts.join(" "),
+                histogram.iter().sum::<u128>()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        // The puzzle's lanternfish, against simulating them the puzzle's way
+        let species = parse("3,4,3,1,2").unwrap();
+        let history = History::record(species, 256).unwrap();
+        let mut school = vec![0, 1, 1, 2, 1, 0, 0, 0, 0];
+        for histogram in &history.days {
+            let histogram: Vec<_> = histogram[0].iter().map(|&n| n as usize).collect();
+            assert_eq!(school, histogram);
+            school = crate::simulate_n(school, 1).unwrap();
+        }
+        assert_eq!(vec![26984457539], history.totals());
+
+        // Young at 2 in pairs, back to 1 after, and dead at 3 days old
+        let species =
+            parse("guppy reset=1 newborn=2 litter=2 max-age=3: 0\nlanternfish: 0").unwrap();
+        let history = History::record(species, 3).unwrap();
+        let guppies: Vec<_> = history.days.iter().map(|d| d[0].clone()).collect();
+        assert_eq!(
+            vec![vec![1, 0, 0], vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 2]],
+            guppies
+        );
+        assert_eq!(vec![4, 2], history.totals());
+        assert!(history.to_csv().starts_with(
+            "day,species,timer_0,timer_1,timer_2,timer_3,timer_4,timer_5,timer_6,timer_7,timer_8,\
+             total\n\
+             0,guppy,1,0,0,0,0,0,0,0,0,1\n\
+             0,lanternfish,1,0,0,0,0,0,0,0,0,1\n\
+             1,guppy,0,1,2,0,0,0,0,0,0,3\n\
+             1,lanternfish,0,0,0,0,0,0,1,0,1,2\n"
+        ));
+
+        assert_eq!(
+            "line 1, column 7: unknown rule `size`, expected reset, newborn, litter or max-age",
+            parse("guppy size=2: 1").unwrap_err().to_string()
+        );
+        assert_eq!(
+            "line 2, column 12: lanternfish is already a species",
+            parse("1,2\nlanternfish: 3").unwrap_err().to_string()
+        );
+        let species = parse("rabbit litter=1000000000000: 0").unwrap();
+        assert!(History::record(species, 100).is_err());
+
+        // Nothing that would take too much memory
+        let error = |input| parse(input).unwrap_err().to_string();
+        assert_eq!(
+            "line 1, column 15: timers only go up to 1000, found 18446744073709551615",
+            error("guppy newborn=18446744073709551615: 1")
+        );
+        assert_eq!(
+            "line 1, column 15: max-age can be at most 1048576",
+            error("guppy max-age=100000000000: 1")
+        );
+        assert_eq!(
+            "line 1, column 8: timers only go up to 1000, found 99999999999",
+            error("guppy: 99999999999")
+        );
+        assert_eq!(
+            "line 1, column 1: 1001 timers for 1048576 ages is more than the 1048576 a species can \
+             have",
+            error("guppy newborn=1000 max-age=1048576: 1")
+        );
+    }
+}
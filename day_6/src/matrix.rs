@@ -0,0 +1,191 @@
+// The school after any number of days in logarithmic time. A day is a linear map from the counts
+// at each timer to the next day's counts, so `n` days are the `n`th power of its matrix, which
+// squaring finds in about 2 log2(n) matrix products.
+//
+// The counts can be kept exactly, though they grow by about an eighth of a bit a day, so exact
+// counts run out of time long before the days run out of range. Counts modulo some number stay
+// small for any number of days.
+//
+//     cargo run --release --bin runner -- run day_6/matrix -p part_2_days=1000000
+//     cargo run --release --bin runner -- run day_6/matrix -p part_2_days=1000000000000000000 -p modulus=1000000007
+
+use aoc::bigint::BigUint;
+
+/// The numbers a matrix holds.
+pub trait Ring {
+    type Element: Clone;
+
+    fn number(&self, n: u64) -> Self::Element;
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+}
+
+/// Exact counts, of any size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exact;
+
+impl Ring for Exact {
+    type Element = BigUint;
+
+    fn number(&self, n: u64) -> BigUint {
+        BigUint::from(n)
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a + b
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        a * b
+    }
+}
+
+/// Counts modulo a number, usually a prime, of at least 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modulo(pub u64);
+
+impl Ring for Modulo {
+    type Element = u64;
+
+    fn number(&self, n: u64) -> u64 {
+        n % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((u128::from(*a) + u128::from(*b)) % u128::from(self.0)) as u64
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        (u128::from(*a) * u128::from(*b) % u128::from(self.0)) as u64
+    }
+}
+
+/// A square matrix, row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    size: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn identity<R: Ring<Element = T>>(ring: &R, size: usize) -> Self {
+        let (zero, one) = (ring.number(0), ring.number(1));
+        Self {
+            size,
+            cells: (0..size * size)
+                .map(|i| match i / size == i % size {
+                    true => one.clone(),
+                    false => zero.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// One day: every timer counts down, and the fish at 0 reset to 6 and each have a fish at 8.
+    pub fn day<R: Ring<Element = T>>(ring: &R) -> Self {
+        let mut counts = [[0; 9]; 9];
+        for (timer, row) in counts.iter_mut().enumerate().take(8) {
+            row[timer + 1] = 1;
+        }
+        counts[6][0] += 1;
+        counts[8][0] += 1;
+        Self {
+            size: 9,
+            cells: counts.iter().flatten().map(|&n| ring.number(n)).collect(),
+        }
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> &T {
+        &self.cells[row * self.size + column]
+    }
+
+    pub fn mul<R: Ring<Element = T>>(&self, ring: &R, other: &Self) -> Self {
+        assert_eq!(self.size, other.size, "the matrices are different sizes");
+        let n = self.size;
+        let mut cells = Vec::with_capacity(n * n);
+        for row in 0..n {
+            for column in 0..n {
+                let mut sum = ring.number(0);
+                for k in 0..n {
+                    sum = ring.add(&sum, &ring.mul(self.get(row, k), other.get(k, column)));
+                }
+                cells.push(sum);
+            }
+        }
+        Self { size: n, cells }
+    }
+
+    /// This matrix to the power `n`, by squaring.
+    pub fn pow<R: Ring<Element = T>>(&self, ring: &R, mut n: u64) -> Self {
+        let mut result = Self::identity(ring, self.size);
+        let mut square = self.clone();
+        while n > 0 {
+            if n & 1 == 1 {
+                result = result.mul(ring, &square);
+            }
+            n >>= 1;
+            if n > 0 {
+                square = square.mul(ring, &square);
+            }
+        }
+        result
+    }
+
+    /// The matrix applied to `counts`.
+    pub fn apply<R: Ring<Element = T>>(&self, ring: &R, counts: &[T]) -> Vec<T> {
+        assert_eq!(self.size, counts.len(), "the counts are the wrong size");
+        (0..self.size)
+            .map(|row| {
+                counts
+                    .iter()
+                    .enumerate()
+                    .fold(ring.number(0), |sum, (k, count)| {
+                        ring.add(&sum, &ring.mul(self.get(row, k), count))
+                    })
+            })
+            .collect()
+    }
+}
+
+/// How many fish `school`, counted by timer, grows to after `days`.
+pub fn population<R: Ring>(ring: &R, school: &[usize], days: u64) -> R::Element {
+    let counts: Vec<_> = school.iter().map(|&n| ring.number(n as u64)).collect();
+    Matrix::day(ring)
+        .pow(ring, days)
+        .apply(ring, &counts)
+        .iter()
+        .fold(ring.number(0), |sum, n| ring.add(&sum, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let school = vec![0, 1, 1, 2, 1, 0, 0, 0, 0];
+        assert_eq!("26984457539", population(&Exact, &school, 256).to_string());
+
+        // Against simulating a day at a time, as far as `usize` goes
+        let p = Modulo(1_000_000_007);
+        for days in (0..=400).step_by(7) {
+            let simulated = crate::sum(&crate::simulate_n(school.clone(), days).unwrap()).unwrap();
+            assert_eq!(
+                simulated.to_string(),
+                population(&Exact, &school, days as u64).to_string()
+            );
+            assert_eq!(simulated as u64 % p.0, population(&p, &school, days as u64));
+        }
+
+        // A quintillion days, as half as many twice
+        let day = Matrix::day(&p);
+        let half = day.pow(&p, 500_000_000_000_000_000);
+        let counts: Vec<_> = school.iter().map(|&n| n as u64).collect();
+        let twice = half.apply(&p, &half.apply(&p, &counts));
+        assert_eq!(
+            twice.iter().fold(0, |sum, n| p.add(&sum, n)),
+            population(&p, &school, 1_000_000_000_000_000_000)
+        );
+        assert_eq!(0, population(&Modulo(2), &[2, 0, 0, 0, 0, 0, 0, 0, 0], 0));
+    }
+}
@@ -0,0 +1,40 @@
+use aoc::params::Params;
+use aoc::parse::Parser;
+use aoc::{Answers, Day};
+
+pub const DAY: Day = Day {
+    name: "day_7",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["336131", "92676646"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let input = Parser::parse(input, |p| p.list(",", |n| n.integer::<i32>()))?;
+
+    let max = *input.iter().max().unwrap();
+
+    let mut part_1 = i32::MAX;
+    let mut part_2 = i32::MAX;
+
+    for i in 0..max {
+        let mut fuel_1 = 0;
+        let mut fuel_2 = 0;
+
+        for position in &input {
+            let distance = (*position - i).abs();
+
+            fuel_1 += distance;
+
+            // https://en.wikipedia.org/wiki/Triangular_number
+            fuel_2 += distance * (distance + 1) / 2;
+        }
+
+        part_1 = part_1.min(fuel_1);
+        part_2 = part_2.min(fuel_2);
+    }
+
+    Ok(Answers::new(part_1, part_2))
+}
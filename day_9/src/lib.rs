@@ -0,0 +1,82 @@
+use aoc::grid::Grid;
+use aoc::params::Params;
+use aoc::parse::{self, Parser};
+use aoc::{Answers, Day};
+
+pub const DAY: Day = Day {
+    name: "day_9",
+    input: include_str!("../input"),
+    params: &[],
+    answers: ["537", "1142757"],
+    solve,
+    alternatives: &[],
+};
+
+fn solve(input: &str, _params: &Params) -> aoc::Result<Answers> {
+    let grid = parse(input)?;
+
+    let part_1 = part_1(&grid);
+    let part_2 = part_2(&grid);
+
+    Ok(Answers::new(part_1, part_2))
+}
+
+fn parse(input: &str) -> parse::Result<Grid<u8>> {
+    Parser::parse(input, |p| p.grid(|c| c.to_digit(10).map(|d| d as u8)))
+}
+
+// https://en.wikipedia.org/wiki/Flood_fill
+fn flood_fill(grid: &Grid<u8>, visited: &mut Grid<bool>, x: usize, y: usize) -> u32 {
+    if grid[(x, y)] == 9 || visited[(x, y)] {
+        return 0;
+    }
+
+    visited[(x, y)] = true;
+
+    let mut n = 0;
+    for (x, y) in grid.adjacent(x, y) {
+        n += flood_fill(grid, visited, x, y);
+    }
+    n + 1
+}
+
+fn part_1(grid: &Grid<u8>) -> u32 {
+    let mut lowest = Vec::new();
+    for (x, y) in grid.positions() {
+        let current = grid[(x, y)];
+        if grid.adjacent(x, y).all(|p| grid[p] > current) {
+            lowest.push(current as u32 + 1);
+        }
+    }
+    lowest.iter().sum()
+}
+
+fn part_2(grid: &Grid<u8>) -> u32 {
+    let mut basin_sizes = Vec::new();
+    let mut visited = Grid::filled(grid.width(), grid.height(), false);
+    for (x, y) in grid.positions() {
+        basin_sizes.push(flood_fill(grid, &mut visited, x, y));
+    }
+    basin_sizes.sort_unstable();
+    basin_sizes.iter().rev().take(3).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let grid = parse(
+            "2199943210
+             3987894921
+             9856789892
+             8767896789
+             9899965678",
+        )
+        .unwrap();
+
+        assert_eq!(15, part_1(&grid));
+        assert_eq!(1134, part_2(&grid));
+    }
+}
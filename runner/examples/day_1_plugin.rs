@@ -0,0 +1,34 @@
+// Day 1 again, built as a plugin to show how one is put together.
+//
+//     cargo build --release --example day_1_plugin
+//     cargo run --release --bin runner -- verify day_1_windows \
+//         --plugin target/release/examples/libday_1_plugin.so
+
+use aoc::params::Params;
+use aoc::parse::Parser;
+use aoc::{Answers, Day};
+
+const DAY: Day = Day {
+    solve,
+    ..day_1::DAY
+};
+
+aoc::export_plugin!(DAY, "day_1_windows");
+
+fn solve(input: &str, params: &Params) -> aoc::Result<Answers> {
+    let depths = Parser::parse(input, |p| p.lines(|l| l.integer::<i32>()))?;
+
+    // Comparing sums of windows that overlap is the same as comparing the ends of the windows
+    let increases = |window: usize| {
+        depths
+            .iter()
+            .zip(depths.iter().skip(window))
+            .filter(|(a, b)| b > a)
+            .count()
+    };
+
+    Ok(Answers::new(
+        increases(params.get("part_1_window")?),
+        increases(params.get("part_2_window")?),
+    ))
+}
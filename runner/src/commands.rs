@@ -0,0 +1,287 @@
+// The commands that run solvers and report on them.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc::params::Params;
+use aoc::runner::print_answer;
+use aoc::{Answers, Day};
+
+use crate::memory::format_bytes;
+use crate::run::{self, format_duration, summary, Outcome, Status};
+use crate::solver::Solver;
+use crate::Options;
+
+const ANSWER_WIDTH: usize = 18;
+
+/// Solve and print the answers.
+pub fn run(solvers: &[&Solver], options: &Options) -> Result<bool, String> {
+    let input = read_input(options)?;
+    let mut ok = true;
+    for &solver in solvers {
+        let params = params(solver, options)?;
+        let outcome = run::run(
+            solver,
+            input.as_deref().unwrap_or(solver.day.input),
+            &params,
+        );
+        println!("{}", heading(solver, &outcome));
+        if let Ok(answers) = &outcome.result {
+            print_answer(1, &answers.part_1);
+            print_answer(2, &answers.part_2);
+        }
+        ok &= outcome.status != Status::Failed && outcome.status != Status::Wrong;
+    }
+    Ok(ok)
+}
+
+/// Check every answer for the bundled inputs.
+pub fn verify(solvers: &[&Solver], options: &Options) -> Result<bool, String> {
+    if !options.inputs.is_empty() || !options.params.is_empty() {
+        return Err("verify only uses the bundled inputs and default parameters".to_string());
+    }
+    let mut correct = 0;
+    for &solver in solvers {
+        let outcome = run::run(solver, solver.day.input, &Params::new(solver.day.params));
+        println!("{}", heading(solver, &outcome));
+        if outcome.status == Status::Correct {
+            correct += 1;
+        }
+    }
+    println!("{} of {} correct", correct, solvers.len());
+    Ok(correct == solvers.len())
+}
+
+/// Time several runs of each solver.
+pub fn bench(solvers: &[&Solver], options: &Options) -> Result<bool, String> {
+    let input = read_input(options)?;
+    let width = solvers.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let width = width.max("Solver".len());
+    println!(
+        "{:width$}  {:7}  {:>10}  {:>10}  {:>10}",
+        "Solver",
+        "Status",
+        "Min",
+        "Mean",
+        "Max",
+        width = width
+    );
+
+    let mut ok = true;
+    for &solver in solvers {
+        let params = params(solver, options)?;
+        let input = input.as_deref().unwrap_or(solver.day.input);
+        let mut times = Vec::new();
+        let mut status = Status::Correct;
+        for _ in 0..options.runs {
+            let outcome = run::run(solver, input, &params);
+            times.push(outcome.elapsed);
+            status = outcome.status;
+            if status == Status::Failed || status == Status::Wrong {
+                break;
+            }
+        }
+        ok &= status != Status::Failed && status != Status::Wrong;
+
+        let total: Duration = times.iter().sum();
+        println!(
+            "{:width$}  {:7}  {:>10}  {:>10}  {:>10}",
+            solver.name,
+            status.name(),
+            format_duration(*times.iter().min().unwrap()),
+            format_duration(total / times.len() as u32),
+            format_duration(*times.iter().max().unwrap()),
+            width = width
+        );
+    }
+    Ok(ok)
+}
+
+/// Run every implementation of each day on the same inputs and check that they agree.
+pub fn compare(all: &[Solver], selected: &[&Solver], options: &Options) -> Result<bool, String> {
+    let implementations =
+        |day: &Day| -> Vec<&Solver> { all.iter().filter(|s| s.day.name == day.name).collect() };
+    let mut days: Vec<&Day> = Vec::new();
+    for solver in selected {
+        if !days.iter().any(|d| d.name == solver.day.name) {
+            days.push(solver.day);
+        }
+    }
+    if options.names.is_empty() {
+        days.retain(|day| implementations(day).len() > 1);
+    }
+    if days.is_empty() {
+        return Err("there's only one implementation of every day".to_string());
+    }
+
+    let inputs = if options.inputs.is_empty() {
+        vec![("the bundled input".to_string(), None)]
+    } else {
+        options
+            .inputs
+            .iter()
+            .map(|path| Ok((path.display().to_string(), Some(read(path)?))))
+            .collect::<Result<_, String>>()?
+    };
+
+    let mut ok = true;
+    for day in days {
+        let solvers = implementations(day);
+        let params = params(solvers[0], options)?;
+        for (input_name, input) in &inputs {
+            let input = input.as_deref().unwrap_or(day.input);
+            let outcomes: Vec<_> = solvers
+                .iter()
+                .map(|&solver| fastest(solver, input, &params, options.runs))
+                .collect();
+
+            println!("{} on {}", day.name, input_name);
+            print_comparison(&solvers, &outcomes);
+
+            // Everything is checked against the first implementation that didn't fail
+            let mut expected: Option<(&Solver, &Answers)> = None;
+            for (solver, outcome) in solvers.iter().zip(&outcomes) {
+                let answers = match &outcome.result {
+                    Ok(answers) => answers,
+                    Err(e) => {
+                        println!("{} failed on {}: {}", solver.name, input_name, e);
+                        ok = false;
+                        continue;
+                    }
+                };
+                let (first, first_answers) = match expected {
+                    Some(expected) => expected,
+                    None => {
+                        expected = Some((solver, answers));
+                        continue;
+                    }
+                };
+                for (part, (a, b)) in [
+                    (&first_answers.part_1, &answers.part_1),
+                    (&first_answers.part_2, &answers.part_2),
+                ]
+                .iter()
+                .enumerate()
+                {
+                    if a != b {
+                        println!(
+                            "{} disagrees with {} on {}: part {} is {}, not {}",
+                            solver.name,
+                            first.name,
+                            input_name,
+                            part + 1,
+                            b,
+                            a
+                        );
+                        ok = false;
+                    }
+                }
+            }
+            println!();
+        }
+    }
+    Ok(ok)
+}
+
+// The fastest of `runs` runs, or the first failure
+fn fastest(solver: &Solver, input: &str, params: &Params, runs: u32) -> Outcome {
+    let mut best = run::run(solver, input, params);
+    for _ in 1..runs {
+        if best.result.is_err() {
+            break;
+        }
+        let outcome = run::run(solver, input, params);
+        if outcome.elapsed < best.elapsed {
+            best = outcome;
+        }
+    }
+    best
+}
+
+fn print_comparison(solvers: &[&Solver], outcomes: &[Outcome]) {
+    let answers: Vec<_> = outcomes
+        .iter()
+        .map(|outcome| match &outcome.result {
+            Ok(answers) => (
+                summary(&answers.part_1, ANSWER_WIDTH),
+                summary(&answers.part_2, ANSWER_WIDTH),
+            ),
+            Err(_) => (String::new(), String::new()),
+        })
+        .collect();
+    let name_width = solvers.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let name_width = name_width.max("Solver".len());
+    let answer_width = answers
+        .iter()
+        .map(|(a, b)| a.chars().count().max(b.chars().count()))
+        .max()
+        .unwrap_or(0)
+        .max(6);
+
+    println!(
+        "{:n$}  {:a$}  {:a$}  {:7}  {:>10}  {:>10}",
+        "Solver",
+        "Part 1",
+        "Part 2",
+        "Status",
+        "Time",
+        "Memory",
+        n = name_width,
+        a = answer_width
+    );
+    for ((solver, outcome), (part_1, part_2)) in solvers.iter().zip(outcomes).zip(answers) {
+        let memory = outcome.memory.map(format_bytes);
+        println!(
+            "{:n$}  {:a$}  {:a$}  {:7}  {:>10}  {:>10}",
+            solver.name,
+            part_1,
+            part_2,
+            outcome.status.name(),
+            format_duration(outcome.elapsed),
+            memory.as_deref().unwrap_or("-"),
+            n = name_width,
+            a = answer_width
+        );
+    }
+}
+
+fn read_input(options: &Options) -> Result<Option<String>, String> {
+    match &options.inputs[..] {
+        [] => Ok(None),
+        [path] => read(path).map(Some),
+        _ => Err("only compare takes more than one --input".to_string()),
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))
+}
+
+fn params(solver: &Solver, options: &Options) -> Result<Params, String> {
+    let mut params = Params::new(solver.day.params);
+    for param in &options.params {
+        params
+            .set_from_str(param)
+            .map_err(|e| format!("{}: {}", solver.name, e))?;
+    }
+    Ok(params)
+}
+
+// e.g. `day_1: ok in 1.20ms`, with the reason for anything that went wrong
+fn heading(solver: &Solver, outcome: &Outcome) -> String {
+    let heading = format!(
+        "{}: {} in {}",
+        solver.name,
+        outcome.status.name(),
+        format_duration(outcome.elapsed)
+    );
+    match &outcome.result {
+        Err(e) => format!("{} ({})", heading, e),
+        Ok(answers) if outcome.status == Status::Wrong => {
+            let e = aoc::runner::verify(solver.day, answers).unwrap_err();
+            format!("{} ({})", heading, e)
+        }
+        Ok(_) => heading,
+    }
+}
@@ -0,0 +1,484 @@
+// An interactive view of every day: run them, point them at other inputs and step through the
+// frames they drew. The last results are kept in `target/dashboard.tsv` between sessions.
+//
+//     enter  run the selected day          a          run every day
+//     i      choose an input file          left/right step through the frames
+//     up/down select a day                 pgup/pgdn  scroll the output
+//     q      quit
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc::params::Params;
+use aoc::Answers;
+
+use crate::memory::format_bytes;
+use crate::run::{self, format_duration, summary, Outcome, Status};
+use crate::solver::Solver;
+use crate::terminal::{Key, Terminal};
+
+const ANSWER_WIDTH: usize = 18;
+
+struct Entry {
+    solver: Solver,
+    /// The input file to use instead of the bundled input.
+    input: Option<PathBuf>,
+    outcome: Option<Outcome>,
+    frame: usize,
+}
+
+struct Dashboard {
+    entries: Vec<Entry>,
+    selected: usize,
+    /// The first visible line of the output pane.
+    scroll: usize,
+    /// The input file being typed in, if any.
+    prompt: Option<String>,
+    /// Shown in place of the help line until the next key press.
+    message: Option<String>,
+}
+
+pub fn main(solvers: Vec<Solver>) -> io::Result<()> {
+    let mut dashboard = Dashboard::new(solvers);
+    dashboard.load();
+
+    let terminal = Terminal::enter()?;
+    // Failures are shown in the dashboard, not sprayed over it
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = dashboard.event_loop(&terminal);
+    panic::set_hook(hook);
+    drop(terminal);
+
+    dashboard.save()?;
+    result
+}
+
+impl Dashboard {
+    fn new(solvers: Vec<Solver>) -> Self {
+        Self {
+            entries: solvers
+                .into_iter()
+                .map(|solver| Entry {
+                    solver,
+                    input: None,
+                    outcome: None,
+                    frame: 0,
+                })
+                .collect(),
+            selected: 0,
+            scroll: 0,
+            prompt: None,
+            message: None,
+        }
+    }
+
+    fn event_loop(&mut self, terminal: &Terminal) -> io::Result<()> {
+        loop {
+            terminal.draw(&self.render(terminal.size()))?;
+            let key = terminal.read_key()?;
+            self.message = None;
+
+            if let Some(prompt) = &mut self.prompt {
+                match key {
+                    Key::Enter => {
+                        let input = prompt.trim();
+                        let entry = &mut self.entries[self.selected];
+                        entry.input = if input.is_empty() {
+                            None
+                        } else {
+                            Some(PathBuf::from(input))
+                        };
+                        self.prompt = None;
+                    }
+                    Key::Escape | Key::Interrupt => self.prompt = None,
+                    Key::Backspace => {
+                        prompt.pop();
+                    }
+                    Key::Char(c) if !c.is_control() => prompt.push(c),
+                    _ => {}
+                }
+                continue;
+            }
+
+            match key {
+                Key::Char('q') | Key::Escape | Key::Interrupt => return Ok(()),
+                Key::Up | Key::Char('k') => self.select(self.selected.saturating_sub(1)),
+                Key::Down | Key::Char('j') => self.select(self.selected + 1),
+                Key::Enter | Key::Char('r') => self.run(self.selected),
+                Key::Char('a') => {
+                    for i in 0..self.entries.len() {
+                        self.message = Some(format!("Running {}...", self.entries[i].solver.name));
+                        terminal.draw(&self.render(terminal.size()))?;
+                        self.run(i);
+                    }
+                    self.message = None;
+                }
+                Key::Char('i') => {
+                    let entry = &self.entries[self.selected];
+                    self.prompt = Some(
+                        entry
+                            .input
+                            .as_ref()
+                            .map(|p| p.display().to_string())
+                            .unwrap_or_default(),
+                    );
+                }
+                Key::Left | Key::Char('h') => self.step_frame(|frame, _| frame.saturating_sub(1)),
+                Key::Right | Key::Char('l') => self.step_frame(|frame, _| frame + 1),
+                Key::Home => self.step_frame(|_, _| 0),
+                Key::End => self.step_frame(|_, frames| frames),
+                Key::PageUp => self.scroll = self.scroll.saturating_sub(10),
+                Key::PageDown => self.scroll += 10,
+                _ => {}
+            }
+        }
+    }
+
+    fn select(&mut self, i: usize) {
+        let i = i.min(self.entries.len() - 1);
+        if i != self.selected {
+            self.selected = i;
+            self.scroll = 0;
+        }
+    }
+
+    fn step_frame(&mut self, step: impl Fn(usize, usize) -> usize) {
+        let entry = &mut self.entries[self.selected];
+        let frames = match &entry.outcome {
+            Some(Outcome {
+                result: Ok(answers),
+                ..
+            }) => answers.frames.len(),
+            _ => 0,
+        };
+        if frames > 0 {
+            entry.frame = step(entry.frame, frames - 1).min(frames - 1);
+        }
+    }
+
+    fn run(&mut self, i: usize) {
+        let entry = &mut self.entries[i];
+        let solver = &entry.solver;
+        let params = Params::new(solver.day.params).with_frames();
+        let outcome = match &entry.input {
+            None => run::run(solver, solver.day.input, &params),
+            Some(path) => match fs::read_to_string(path) {
+                Ok(input) => run::run(solver, &input, &params),
+                Err(e) => Outcome {
+                    result: Err(format!("couldn't read {}: {}", path.display(), e)),
+                    status: Status::Failed,
+                    elapsed: Duration::default(),
+                    memory: None,
+                },
+            },
+        };
+        entry.outcome = Some(outcome);
+        entry.frame = 0;
+        if i == self.selected {
+            self.scroll = 0;
+        }
+    }
+
+    fn render(&self, (width, height): (usize, usize)) -> Vec<String> {
+        let mut lines = vec![format!(
+            "\x1b[7m{:width$}",
+            " Advent of Code 2021",
+            width = width
+        )];
+
+        // The list of days, scrolled to keep the selection in view
+        let list_height = self
+            .entries
+            .len()
+            .min((height.saturating_sub(4) / 2).max(3));
+        let top = (self.selected + 1).saturating_sub(list_height);
+        let name_width = self.entries.iter().map(|e| e.solver.name.len()).max();
+        let name_width = name_width.unwrap_or(0).max(7);
+        lines.push(format!(
+            "   {:n$} {:w$} {:w$} {:7} {}",
+            "Day",
+            "Part 1",
+            "Part 2",
+            "Status",
+            "Time",
+            n = name_width,
+            w = ANSWER_WIDTH
+        ));
+        for (i, entry) in self.entries.iter().enumerate().skip(top).take(list_height) {
+            let (part_1, part_2, status, time) = match &entry.outcome {
+                None => (String::new(), String::new(), "", String::new()),
+                Some(outcome) => {
+                    let (part_1, part_2) = match &outcome.result {
+                        Ok(answers) => (
+                            summary(&answers.part_1, ANSWER_WIDTH),
+                            summary(&answers.part_2, ANSWER_WIDTH),
+                        ),
+                        Err(_) => (String::new(), String::new()),
+                    };
+                    (
+                        part_1,
+                        part_2,
+                        outcome.status.name(),
+                        format_duration(outcome.elapsed),
+                    )
+                }
+            };
+            let line = format!(
+                "{} {:n$} {:w$} {:w$} {:7} {:9}",
+                if i == self.selected { ">" } else { " " },
+                entry.solver.name,
+                part_1,
+                part_2,
+                status,
+                time,
+                n = name_width,
+                w = ANSWER_WIDTH
+            );
+            if i == self.selected {
+                lines.push(format!(" \x1b[7m{}", line));
+            } else {
+                lines.push(format!(" {}", line));
+            }
+        }
+
+        // Everything about the selected day
+        let entry = &self.entries[self.selected];
+        let input = match &entry.input {
+            Some(path) => path.display().to_string(),
+            None => "bundled".to_string(),
+        };
+        let name = match entry.solver.source() {
+            Some(path) => format!("{} from {}", entry.solver.name, path.display()),
+            None => entry.solver.name.clone(),
+        };
+        let title = format!("── {} ── input: {} ", name, input);
+        let rule = width.saturating_sub(title.chars().count());
+        lines.push(format!("{}{}", title, "─".repeat(rule)));
+
+        let pane = self.pane(entry);
+        let pane_height = height.saturating_sub(lines.len() + 1);
+        let scroll = self.scroll.min(pane.len().saturating_sub(pane_height));
+        lines.extend(pane.into_iter().skip(scroll).take(pane_height));
+        lines.resize(height.saturating_sub(1), String::new());
+
+        lines.push(match (&self.prompt, &self.message) {
+            (Some(prompt), _) => format!(
+                "Input file for {} (empty for the bundled input): {}\x1b[7m \x1b[0m",
+                entry.solver.name, prompt
+            ),
+            (None, Some(message)) => message.clone(),
+            (None, None) => {
+                "enter run  a run all  i input  ←/→ frame  pgup/pgdn scroll  q quit".to_string()
+            }
+        });
+        lines
+    }
+
+    fn pane(&self, entry: &Entry) -> Vec<String> {
+        let outcome = match &entry.outcome {
+            Some(outcome) => outcome,
+            None => return vec!["Not run yet, press enter to run it.".to_string()],
+        };
+        let mut status = format!(
+            "Status: {} in {}",
+            outcome.status.name(),
+            format_duration(outcome.elapsed)
+        );
+        if let Some(memory) = outcome.memory {
+            status += &format!(" using {}", format_bytes(memory));
+        }
+        let mut lines = vec![status];
+        let answers = match &outcome.result {
+            Ok(answers) => answers,
+            Err(e) => {
+                lines.extend(format!("Error: {}", e).lines().map(String::from));
+                return lines;
+            }
+        };
+
+        for (part, answer) in [&answers.part_1, &answers.part_2].iter().enumerate() {
+            if answer.contains('\n') {
+                lines.push(format!("Part {}:", part + 1));
+                lines.extend(answer.lines().map(String::from));
+            } else {
+                lines.push(format!("Part {}: {}", part + 1, answer));
+            }
+        }
+        if outcome.status == Status::Wrong {
+            let [part_1, part_2] = entry.solver.day.answers;
+            lines.push(format!("Expected: {} and {}", part_1, part_2));
+        }
+
+        if !answers.frames.is_empty() {
+            lines.push(String::new());
+            lines.push(format!(
+                "Frame {}/{}",
+                entry.frame + 1,
+                answers.frames.len()
+            ));
+            lines.extend(answers.frames[entry.frame].lines().map(String::from));
+        }
+        lines
+    }
+
+    fn load(&mut self) {
+        let contents = match fs::read_to_string(results_path()) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+        let mut saved: HashMap<_, _> = contents.lines().filter_map(from_line).collect();
+        for entry in &mut self.entries {
+            if let Some((input, outcome)) = saved.remove(&entry.solver.name) {
+                entry.input = input;
+                entry.outcome = outcome;
+            }
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = results_path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let lines: String = self
+            .entries
+            .iter()
+            .map(|e| to_line(&e.solver.name, e.input.as_deref(), e.outcome.as_ref()) + "\n")
+            .collect();
+        fs::write(path, lines)
+    }
+}
+
+fn results_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/dashboard.tsv")
+}
+
+// One tab separated line per day: name, input, status, microseconds, bytes (if measured), then
+// both answers (or the error). Frames aren't kept.
+fn to_line(name: &str, input: Option<&Path>, outcome: Option<&Outcome>) -> String {
+    let input = input.map(|p| p.display().to_string()).unwrap_or_default();
+    let mut fields = vec![name.to_string(), input];
+    if let Some(outcome) = outcome {
+        fields.push(outcome.status.name().to_string());
+        fields.push(outcome.elapsed.as_micros().to_string());
+        fields.push(outcome.memory.map(|m| m.to_string()).unwrap_or_default());
+        match &outcome.result {
+            Ok(answers) => {
+                fields.push(answers.part_1.clone());
+                fields.push(answers.part_2.clone());
+            }
+            Err(e) => fields.push(e.clone()),
+        }
+    }
+    fields
+        .iter()
+        .map(|f| escape(f))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+type Saved = (String, (Option<PathBuf>, Option<Outcome>));
+
+fn from_line(line: &str) -> Option<Saved> {
+    let fields: Vec<_> = line.split('\t').map(unescape).collect();
+    let (name, input) = match &fields[..] {
+        [name, input, ..] => (name.clone(), input),
+        _ => return None,
+    };
+    let input = Some(input).filter(|i| !i.is_empty()).map(PathBuf::from);
+
+    let outcome = match &fields[2..] {
+        [] => None,
+        [status, micros, memory, rest @ ..] => {
+            let status = Status::from_name(status)?;
+            let elapsed = Duration::from_micros(micros.parse().ok()?);
+            let memory = match memory.as_str() {
+                "" => None,
+                memory => Some(memory.parse().ok()?),
+            };
+            let result = match rest {
+                [part_1, part_2] if status != Status::Failed => Ok(Answers::new(part_1, part_2)),
+                [error] if status == Status::Failed => Err(error.clone()),
+                _ => return None,
+            };
+            Some(Outcome {
+                result,
+                status,
+                elapsed,
+                memory,
+            })
+        }
+        _ => return None,
+    };
+    Some((name, (input, outcome)))
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some(c) => result.push(c),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let outcome = Outcome {
+            result: Ok(Answers::new("17", "#.\\\n.#")),
+            status: Status::Unverified,
+            elapsed: Duration::from_micros(1234),
+            memory: Some(4096),
+        };
+        let line = to_line("day_13", Some(Path::new("my input")), Some(&outcome));
+        assert!(!line.contains('\n'));
+        assert_eq!(
+            Some((
+                "day_13".to_string(),
+                (Some(PathBuf::from("my input")), Some(outcome))
+            )),
+            from_line(&line)
+        );
+
+        let failed = Outcome {
+            result: Err("panicked:\tboom".to_string()),
+            status: Status::Failed,
+            elapsed: Duration::default(),
+            memory: None,
+        };
+        let line = to_line("day_1", None, Some(&failed));
+        assert_eq!(
+            Some(("day_1".to_string(), (None, Some(failed)))),
+            from_line(&line)
+        );
+        assert_eq!(
+            Some(("day_2".to_string(), (None, None))),
+            from_line(&to_line("day_2", None, None))
+        );
+        assert_eq!(None, from_line("day_3\t\tmaybe\t1\t2\t3"));
+    }
+}
@@ -0,0 +1,22 @@
+use aoc::Day;
+
+pub const DAYS: [&Day; 18] = [
+    &day_1::DAY,
+    &day_2::DAY,
+    &day_3::DAY,
+    &day_4::DAY,
+    &day_5::DAY,
+    &day_6::DAY,
+    &day_7::DAY,
+    &day_8::DAY,
+    &day_9::DAY,
+    &day_10::DAY,
+    &day_11::DAY,
+    &day_12::DAY,
+    &day_13::DAY,
+    &day_14::DAY,
+    &day_15::DAY,
+    &day_16::DAY,
+    &day_17::DAY,
+    &day_18::DAY,
+];
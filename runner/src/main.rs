@@ -0,0 +1,150 @@
+// Runs the days, and solvers loaded from plugins, from one place.
+//
+//     cargo run --release --bin runner -- run day_6 --param part_2_days=300
+//     cargo run --release --bin runner -- verify                   # check every answer
+//     cargo run --release --bin runner -- bench day_15 --runs 20
+//     cargo run --release --bin runner -- compare day_18 --input a --input b
+//     cargo run --release --bin runner -- dashboard                # browse, run and inspect
+//     cargo run --release --bin runner -- verify --plugin target/release/examples/libday_1_plugin.so
+
+mod commands;
+mod dashboard;
+mod days;
+mod memory;
+mod plugin;
+mod run;
+mod solver;
+mod terminal;
+
+use std::path::PathBuf;
+use std::{env, process};
+
+use solver::Solver;
+
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
+pub struct Options {
+    command: String,
+    /// The solvers to use, or all of them if empty.
+    names: Vec<String>,
+    plugins: Vec<PathBuf>,
+    params: Vec<String>,
+    inputs: Vec<PathBuf>,
+    runs: u32,
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|e| exit(&e));
+    let solvers = solver::load(&options.plugins).unwrap_or_else(|e| exit(&e));
+    let selected = select(&solvers, &options.names).unwrap_or_else(|e| exit(&e));
+
+    let result = match options.command.as_str() {
+        "run" => commands::run(&selected, &options),
+        "verify" => commands::verify(&selected, &options),
+        "bench" => commands::bench(&selected, &options),
+        "compare" => commands::compare(&solvers, &selected, &options),
+        "dashboard" => dashboard::main(solvers)
+            .map(|()| true)
+            .map_err(|e| e.to_string()),
+        _ => unreachable!(),
+    };
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => exit(&e),
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        command: String::new(),
+        names: Vec::new(),
+        plugins: Vec::new(),
+        params: Vec::new(),
+        inputs: Vec::new(),
+        runs: 10,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("{} needs an argument", arg))
+        };
+        match arg.as_str() {
+            "--plugin" => options.plugins.push(PathBuf::from(value()?)),
+            "--param" | "-p" => options.params.push(value()?),
+            "--input" | "-i" => options.inputs.push(PathBuf::from(value()?)),
+            "--runs" => {
+                options.runs = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&runs| runs > 0)
+                    .ok_or("--runs needs a positive number")?
+            }
+            "--help" | "-h" => {
+                usage();
+                process::exit(0);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unexpected argument `{}`", arg)),
+            "run" | "verify" | "bench" | "compare" | "dashboard" if options.command.is_empty() => {
+                options.command = arg
+            }
+            _ if options.command.is_empty() => return Err(format!("unknown command `{}`", arg)),
+            _ => options.names.push(arg),
+        }
+    }
+
+    if options.command.is_empty() {
+        usage();
+        process::exit(1);
+    }
+    Ok(options)
+}
+
+fn select<'a>(solvers: &'a [Solver], names: &[String]) -> Result<Vec<&'a Solver>, String> {
+    if names.is_empty() {
+        return Ok(solvers.iter().collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            solvers.iter().find(|s| &s.name == name).ok_or_else(|| {
+                let known: Vec<_> = solvers.iter().map(|s| s.name.as_str()).collect();
+                format!(
+                    "unknown solver `{}`, expected one of: {}",
+                    name,
+                    known.join(", ")
+                )
+            })
+        })
+        .collect()
+}
+
+fn usage() {
+    println!("Usage: runner COMMAND [SOLVER]... [OPTION]...");
+    println!("\nCommands:");
+    println!("    run        solve and print the answers");
+    println!("    verify     check the answers for the bundled inputs");
+    println!("    bench      time several runs of each solver");
+    println!("    compare    check that every implementation of a day agrees, and compare them");
+    println!("    dashboard  browse, run and inspect every solver");
+    println!("\nSolvers are the days (`day_1`...), their alternative implementations");
+    println!("(`day_18/recursive`...) and any loaded plugins, all of them by default. compare");
+    println!(
+        "takes all the implementations of the days given, or of every day with more than one."
+    );
+    println!("\nOptions:");
+    println!("    --plugin PATH         load a solver from a dynamic library");
+    println!("    --param NAME=VALUE    override a parameter");
+    println!("    --input PATH          use another input instead of the bundled one, compare");
+    println!("                          takes several");
+    println!("    --runs N              how many times bench and compare run each solver");
+    println!("                          (default: 10)");
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("runner: {}", message);
+    process::exit(1);
+}
@@ -0,0 +1,114 @@
+// Counts the memory the runner has allocated, so solvers can be compared by their peak usage.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct Counting;
+
+// How many bytes are allocated, and the most there have been since the last `peak` started
+struct Counter {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+static COUNTER: Counter = Counter::new();
+
+impl Counter {
+    const fn new() -> Self {
+        Self {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    fn grow(&self, bytes: usize) {
+        let current = self.current.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, bytes: usize) {
+        self.current.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    fn peak<T>(&self, f: impl FnOnce() -> T) -> (T, usize) {
+        let before = self.current.load(Ordering::Relaxed);
+        self.peak.store(before, Ordering::Relaxed);
+        let result = f();
+        let peak = self.peak.load(Ordering::Relaxed).saturating_sub(before);
+        (result, peak)
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            COUNTER.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            COUNTER.grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        COUNTER.shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            COUNTER.grow(new_size);
+            COUNTER.shrink(layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Run `f`, also returning the most memory it had allocated at once. Only allocations made through
+/// the runner's allocator count, so anything a plugin allocates is missed.
+pub fn peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    COUNTER.peak(f)
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    if bytes < 1 << 10 {
+        format!("{}B", bytes)
+    } else if bytes < 1 << 20 {
+        format!("{:.1}KiB", bytes as f64 / (1u64 << 10) as f64)
+    } else if bytes < 1 << 30 {
+        format!("{:.1}MiB", bytes as f64 / (1u64 << 20) as f64)
+    } else {
+        format!("{:.1}GiB", bytes as f64 / (1u64 << 30) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        // Other tests allocate through the runner's own counter at the same time
+        let counter = Counter::new();
+        counter.grow(100);
+        let ((), peak) = counter.peak(|| {
+            counter.grow(300);
+            counter.shrink(300);
+            counter.grow(50);
+        });
+        assert_eq!(300, peak);
+        counter.shrink(150);
+        assert_eq!(0, counter.current.load(Ordering::Relaxed));
+
+        assert_eq!("512B", format_bytes(512));
+        assert_eq!("1.5KiB", format_bytes(1536));
+        assert_eq!("3.0MiB", format_bytes(3 << 20));
+    }
+}
@@ -0,0 +1,170 @@
+// Loading solvers from dynamic libraries. `aoc::plugin` has the other side of the ABI. Libraries
+// are opened with `dlopen`, so plugins only load on unix.
+
+use std::os::raw::c_void;
+use std::path::{Path, PathBuf};
+
+use aoc::params::Params;
+use aoc::plugin::{Descriptor, Str};
+#[cfg(unix)]
+use aoc::plugin::{DescriptorFn, VersionFn, ABI_VERSION, DESCRIPTOR_SYMBOL, VERSION_SYMBOL};
+use aoc::Answers;
+
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct Plugin {
+    path: PathBuf,
+    handle: *mut c_void,
+    // Points into the library so it's only valid until `handle` is closed
+    descriptor: *const Descriptor,
+}
+
+impl Plugin {
+    #[cfg(unix)]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let error = |message: String| format!("{}: {}", path.display(), message);
+
+        let handle = dl::open(path).map_err(|e| match e {
+            // The message already names the file
+            Some(message) => message,
+            None => error("invalid path".to_string()),
+        })?;
+        // From here on dropping `plugin` closes the library
+        let mut plugin = Self {
+            path: path.to_path_buf(),
+            handle,
+            descriptor: std::ptr::null(),
+        };
+
+        let version = plugin
+            .symbol(VERSION_SYMBOL)
+            .ok_or_else(|| error(format!("not a plugin, `{}` is missing", VERSION_SYMBOL)))?;
+        let version = unsafe { std::mem::transmute::<*mut c_void, VersionFn>(version)() };
+        if version != ABI_VERSION {
+            return Err(error(format!(
+                "incompatible plugin ABI version {}, expected version {}",
+                version, ABI_VERSION
+            )));
+        }
+
+        let descriptor = plugin
+            .symbol(DESCRIPTOR_SYMBOL)
+            .ok_or_else(|| error(format!("not a plugin, `{}` is missing", DESCRIPTOR_SYMBOL)))?;
+        plugin.descriptor =
+            unsafe { std::mem::transmute::<*mut c_void, DescriptorFn>(descriptor)() };
+        if plugin.descriptor.is_null() {
+            return Err(error("the plugin has no descriptor".to_string()));
+        }
+        plugin.day().map_err(|e| error(e.to_string()))?;
+        plugin.name().map_err(|e| error(e.to_string()))?;
+        Ok(plugin)
+    }
+
+    #[cfg(not(unix))]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        Err(format!(
+            "{}: plugins aren't supported on this platform",
+            path.display()
+        ))
+    }
+
+    #[cfg(unix)]
+    fn symbol(&self, name: &str) -> Option<*mut c_void> {
+        dl::symbol(self.handle, name)
+    }
+
+    fn descriptor(&self) -> &Descriptor {
+        unsafe { &*self.descriptor }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The name of the day this solves.
+    pub fn day(&self) -> Result<&str, std::str::Utf8Error> {
+        unsafe { self.descriptor().day.as_str() }
+    }
+
+    pub fn name(&self) -> Result<&str, std::str::Utf8Error> {
+        unsafe { self.descriptor().name.as_str() }
+    }
+
+    pub fn solve(&self, input: &str, params: &Params) -> aoc::Result<Answers> {
+        let mut lines = String::new();
+        for param in params.declared() {
+            lines += &format!("{}={}\n", param.name, params.value(param.name)?);
+        }
+
+        let descriptor = self.descriptor();
+        unsafe {
+            let output = (descriptor.solve)(Str::new(input), Str::new(&lines));
+            let result = if output.ok {
+                Ok(Answers::new(
+                    output.part_1.to_string_lossy(),
+                    output.part_2.to_string_lossy(),
+                ))
+            } else {
+                Err(output.error.to_string_lossy().into())
+            };
+            (descriptor.free)(output);
+            result
+        }
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        dl::close(self.handle);
+    }
+}
+
+#[cfg(unix)]
+mod dl {
+    use std::ffi::{CStr, CString};
+    use std::os::raw::{c_char, c_int, c_void};
+    use std::path::Path;
+
+    #[link(name = "dl")]
+    extern "C" {
+        fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        fn dlclose(handle: *mut c_void) -> c_int;
+        fn dlerror() -> *mut c_char;
+    }
+
+    const RTLD_NOW: c_int = 2;
+
+    /// The library's handle, or `dlerror`'s message if it couldn't be opened and `None` if the
+    /// path can't be passed to it.
+    pub fn open(path: &Path) -> Result<*mut c_void, Option<String>> {
+        let filename = CString::new(path.to_string_lossy().as_bytes()).map_err(|_| None)?;
+        let handle = unsafe { dlopen(filename.as_ptr(), RTLD_NOW) };
+        if handle.is_null() {
+            return Err(Some(last_error()));
+        }
+        Ok(handle)
+    }
+
+    pub fn symbol(handle: *mut c_void, name: &str) -> Option<*mut c_void> {
+        let name = CString::new(name).unwrap();
+        let symbol = unsafe { dlsym(handle, name.as_ptr()) };
+        Some(symbol).filter(|s| !s.is_null())
+    }
+
+    pub fn close(handle: *mut c_void) {
+        unsafe {
+            dlclose(handle);
+        }
+    }
+
+    fn last_error() -> String {
+        let error = unsafe { dlerror() };
+        if error.is_null() {
+            return "unknown error".to_string();
+        }
+        unsafe { CStr::from_ptr(error) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
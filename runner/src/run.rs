@@ -0,0 +1,117 @@
+// Running a day and keeping track of how it went.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use aoc::params::Params;
+use aoc::Answers;
+
+use crate::memory;
+use crate::solver::Solver;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    /// Solved, but with an input or parameters we don't know the answers for.
+    Unverified,
+    Failed,
+}
+
+impl Status {
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Correct => "ok",
+            Status::Wrong => "wrong",
+            Status::Unverified => "?",
+            Status::Failed => "failed",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Status::Correct,
+            Status::Wrong,
+            Status::Unverified,
+            Status::Failed,
+        ]
+        .iter()
+        .copied()
+        .find(|s| s.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The answers, or why there aren't any.
+    pub result: Result<Answers, String>,
+    pub status: Status,
+    pub elapsed: Duration,
+    /// The most memory in use at once while solving, if it could be measured.
+    pub memory: Option<usize>,
+}
+
+/// Solve `input`, checking the answers if it's the bundled input. Errors and panics both count as
+/// failures.
+pub fn run(solver: &Solver, input: &str, params: &Params) -> Outcome {
+    let day = solver.day;
+    let start = Instant::now();
+    let (result, memory) =
+        memory::peak(|| panic::catch_unwind(AssertUnwindSafe(|| solver.solve(input, params))));
+    let elapsed = start.elapsed();
+    // Plugins have their own allocator
+    let memory = Some(memory).filter(|_| solver.source().is_none());
+
+    let result = match result {
+        Ok(Ok(answers)) => Ok(answers),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(panic) => Err(panic_message(panic)),
+    };
+    let status = match &result {
+        Err(_) => Status::Failed,
+        Ok(_) if input != day.input || !params.is_default() => Status::Unverified,
+        Ok(answers) => match aoc::runner::verify(day, answers) {
+            Ok(()) => Status::Correct,
+            Err(_) => Status::Wrong,
+        },
+    };
+
+    Outcome {
+        result,
+        status,
+        elapsed,
+        memory,
+    }
+}
+
+fn panic_message(panic: Box<dyn std::any::Any + Send>) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown error".to_string());
+    format!("panicked: {}", message)
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+    if micros < 1_000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.2}ms", micros as f64 / 1e3)
+    } else {
+        format!("{:.2}s", micros as f64 / 1e6)
+    }
+}
+
+/// The first line of an answer, cut to `width` characters.
+pub fn summary(answer: &str, width: usize) -> String {
+    if answer.contains('\n') {
+        return "(picture)".to_string();
+    }
+    if answer.chars().count() > width {
+        let cut: String = answer.chars().take(width - 1).collect();
+        return cut + "…";
+    }
+    answer.to_string()
+}
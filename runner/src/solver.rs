@@ -0,0 +1,83 @@
+// Everything the runner can run: the days themselves, their alternative implementations and any
+// plugins. They all share the input, parameters and answers of the day they solve.
+
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use aoc::params::Params;
+use aoc::{Answers, Day};
+
+use crate::days::DAYS;
+use crate::plugin::Plugin;
+
+pub struct Solver {
+    pub name: String,
+    pub day: &'static Day,
+    kind: Kind,
+}
+
+enum Kind {
+    Builtin(fn(&str, &Params) -> aoc::Result<Answers>),
+    Plugin(Rc<Plugin>),
+}
+
+impl Solver {
+    pub fn solve(&self, input: &str, params: &Params) -> aoc::Result<Answers> {
+        match &self.kind {
+            Kind::Builtin(solve) => solve(input, params),
+            Kind::Plugin(plugin) => plugin.solve(input, params),
+        }
+    }
+
+    /// Where the solver comes from, if it isn't built in.
+    pub fn source(&self) -> Option<PathBuf> {
+        match &self.kind {
+            Kind::Builtin(_) => None,
+            Kind::Plugin(plugin) => Some(plugin.path().to_path_buf()),
+        }
+    }
+}
+
+/// Every day and its alternatives (named `day_N/alternative`), followed by the plugins at `paths`.
+pub fn load(paths: &[PathBuf]) -> Result<Vec<Solver>, String> {
+    let mut solvers = Vec::new();
+    for &day in DAYS.iter() {
+        solvers.push(Solver {
+            name: day.name.to_string(),
+            day,
+            kind: Kind::Builtin(day.solve),
+        });
+        for alternative in day.alternatives {
+            solvers.push(Solver {
+                name: format!("{}/{}", day.name, alternative.name),
+                day,
+                kind: Kind::Builtin(alternative.solve),
+            });
+        }
+    }
+
+    for path in paths {
+        let plugin = Rc::new(Plugin::load(path)?);
+        // Both were checked while loading
+        let day_name = plugin.day().unwrap();
+        let name = plugin.name().unwrap().to_string();
+
+        let day = DAYS
+            .iter()
+            .find(|d| d.name == day_name)
+            .ok_or_else(|| format!("{}: unknown day `{}`", path.display(), day_name))?;
+        if solvers.iter().any(|s| s.name == name) {
+            return Err(format!(
+                "{}: there's already a solver called `{}`",
+                path.display(),
+                name
+            ));
+        }
+        solvers.push(Solver {
+            name,
+            day,
+            kind: Kind::Plugin(plugin),
+        });
+    }
+    Ok(solvers)
+}
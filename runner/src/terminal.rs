@@ -0,0 +1,223 @@
+// Just enough terminal handling for the dashboard: raw mode through `stty` and ANSI escape codes
+// for everything else, so there's nothing to depend on.
+
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Escape,
+    Backspace,
+    Interrupt,
+    Char(char),
+}
+
+/// The terminal in raw mode on the alternate screen. Dropping it puts everything back.
+pub struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    pub fn enter() -> io::Result<Self> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        // Reads give up after a tenth of a second so a lone escape isn't mistaken for the start of
+        // a sequence
+        stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Self { saved })
+    }
+
+    /// The width and height of the terminal.
+    pub fn size(&self) -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let mut size = size.split_whitespace().map(|n| n.parse().ok());
+                let height = size.next()??;
+                let width = size.next()??;
+                Some((width, height))
+            })
+            .filter(|&(width, height)| width > 0 && height > 0)
+            .unwrap_or((80, 24))
+    }
+
+    /// Draw `lines` over the whole screen, cutting them to fit.
+    pub fn draw(&self, lines: &[String]) -> io::Result<()> {
+        let (width, height) = self.size();
+        let mut screen = String::new();
+        for row in 0..height {
+            screen.push_str(&format!("\x1b[{};1H\x1b[2K", row + 1));
+            if let Some(line) = lines.get(row) {
+                screen.push_str(&truncate(line, width));
+            }
+            screen.push_str("\x1b[0m");
+        }
+        let mut stdout = io::stdout();
+        stdout.write_all(screen.as_bytes())?;
+        stdout.flush()
+    }
+
+    /// Wait for the next key press.
+    pub fn read_key(&self) -> io::Result<Key> {
+        loop {
+            if let Some(key) = read_key(&mut io::stdin().lock())? {
+                return Ok(key);
+            }
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// Cut `line` to `width` visible characters, skipping over escape codes
+fn truncate(line: &str, width: usize) -> String {
+    let mut result = String::new();
+    let mut visible = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            result.push(c);
+            for c in &mut chars {
+                result.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else if visible < width {
+            result.push(c);
+            visible += 1;
+        }
+    }
+    result
+}
+
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    Ok(match input.read(&mut byte)? {
+        0 => None,
+        _ => Some(byte[0]),
+    })
+}
+
+/// Decode one key press, or `None` if nothing was pressed.
+pub fn read_key(input: &mut impl Read) -> io::Result<Option<Key>> {
+    let byte = match read_byte(input)? {
+        Some(byte) => byte,
+        None => return Ok(None),
+    };
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        0x03 => Key::Interrupt,
+        0x1b => match read_byte(input)? {
+            Some(b'[') | Some(b'O') => match read_byte(input)? {
+                Some(b'A') => Key::Up,
+                Some(b'B') => Key::Down,
+                Some(b'C') => Key::Right,
+                Some(b'D') => Key::Left,
+                Some(b'H') => Key::Home,
+                Some(b'F') => Key::End,
+                Some(n @ b'0'..=b'9') => {
+                    // Sequences like `ESC [ 5 ~`
+                    let mut code = vec![n];
+                    while let Some(b) = read_byte(input)? {
+                        if b == b'~' {
+                            break;
+                        }
+                        code.push(b);
+                    }
+                    match &code[..] {
+                        b"1" | b"7" => Key::Home,
+                        b"4" | b"8" => Key::End,
+                        b"5" => Key::PageUp,
+                        b"6" => Key::PageDown,
+                        _ => return Ok(None),
+                    }
+                }
+                _ => return Ok(None),
+            },
+            _ => Key::Escape,
+        },
+        _ => {
+            // Gather the rest of a multi-byte character
+            let len = match byte {
+                0xf0..=0xff => 4,
+                0xe0..=0xef => 3,
+                0xc0..=0xdf => 2,
+                _ => 1,
+            };
+            let mut bytes = vec![byte];
+            while bytes.len() < len {
+                match read_byte(input)? {
+                    Some(b) => bytes.push(b),
+                    None => break,
+                }
+            }
+            match std::str::from_utf8(&bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+            {
+                Some(c) => Key::Char(c),
+                None => return Ok(None),
+            }
+        }
+    };
+    Ok(Some(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let mut input: &[u8] = b"q\x1b[A\x1b[6~\r\x7f\xc3\xa9\x1b";
+        let mut keys = Vec::new();
+        while let Some(key) = read_key(&mut input).unwrap() {
+            keys.push(key);
+        }
+        assert_eq!(
+            vec![
+                Key::Char('q'),
+                Key::Up,
+                Key::PageDown,
+                Key::Enter,
+                Key::Backspace,
+                Key::Char('é'),
+                Key::Escape,
+            ],
+            keys
+        );
+
+        assert_eq!("\x1b[7mab", truncate("\x1b[7mabc", 2));
+    }
+}